@@ -0,0 +1,116 @@
+use serde::{Serialize, Deserialize};
+
+const EWMA_ALPHA: f64 = 0.2;          // Weight of the newest reading in the baseline
+const WARMUP_SAMPLES: u64 = 5;        // Readings needed before deviations are judged
+const MAX_Z_SCORE: f64 = 4.0;         // Deviation from the EWMA, in standard deviations
+const MAX_STEP_RATIO: f64 = 3.0;      // Jump (either way) versus the previous reading
+const FLATLINE_TOLERANCE: f64 = 1e-4; // Relative change treated as "no change"
+const FLATLINE_RUN: u32 = 6;          // Near-constant readings in a row: stuck sensor
+const DUPLICATE_RUN: u32 = 3;         // Bit-identical readings in a row: replayed values
+
+/// Rolling statistics of one sentinel's accepted readings, normalized to a
+/// rate per hour so packets covering different periods compare. Packets
+/// that deviate sharply from the sentinel's own history are quarantined
+/// for human review instead of being credited or slashed automatically.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SentinelStats {
+    pub samples: u64,
+    pub ewma: f64,          // Exponentially weighted mean rate
+    pub ewm_variance: f64,  // Exponentially weighted variance around it
+    pub last: f64,          // Previous accepted rate
+    pub duplicate_run: u32, // Length of the current run of identical rates
+    pub flat_run: u32,      // Length of the current run of near-constant rates
+}
+
+impl SentinelStats {
+    /// Why `rate` looks anomalous for this sentinel, if it does.
+    pub fn screen(&self, rate: f64) -> Result<(), String> {
+        if self.samples == 0 {
+            return Ok(());
+        }
+        if rate == self.last && self.duplicate_run + 1 >= DUPLICATE_RUN {
+            return Err(format!("Duplicate-value run: {} identical readings of {:.3}/h.", self.duplicate_run + 1, rate));
+        }
+        if self.is_flat(rate) && self.flat_run + 1 >= FLATLINE_RUN {
+            return Err(format!("Flat-lined sensor: {} readings at ~{:.3}/h without variation.", self.flat_run + 1, rate));
+        }
+        if self.samples < WARMUP_SAMPLES {
+            return Ok(());
+        }
+        if self.last > 0.0 && (rate > self.last * MAX_STEP_RATIO || rate < self.last / MAX_STEP_RATIO) {
+            return Err(format!("Step change from {:.3}/h to {:.3}/h.", self.last, rate));
+        }
+        let deviation = self.ewm_variance.sqrt();
+        if deviation > 0.0 {
+            let z = (rate - self.ewma).abs() / deviation;
+            if z > MAX_Z_SCORE {
+                return Err(format!("Reading {:.3}/h deviates {:.1}σ from the sentinel's baseline ({:.3}/h).", rate, z, self.ewma));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fold an accepted reading into the statistics.
+    pub fn observe(&mut self, rate: f64) {
+        if self.samples == 0 {
+            *self = Self { samples: 1, ewma: rate, ewm_variance: 0.0, last: rate, duplicate_run: 1, flat_run: 1 };
+            return;
+        }
+        self.duplicate_run = if rate == self.last { self.duplicate_run + 1 } else { 1 };
+        self.flat_run = if self.is_flat(rate) { self.flat_run + 1 } else { 1 };
+        let diff = rate - self.ewma;
+        let increment = EWMA_ALPHA * diff;
+        self.ewma += increment;
+        self.ewm_variance = (1.0 - EWMA_ALPHA) * (self.ewm_variance + diff * increment);
+        self.last = rate;
+        self.samples += 1;
+    }
+
+    fn is_flat(&self, rate: f64) -> bool {
+        (rate - self.last).abs() <= FLATLINE_TOLERANCE * self.last.abs().max(f64::EPSILON)
+    }
+}
+
+/// Reading normalized to a rate per hour of its measurement period.
+pub fn hourly_rate(quantity: f64, (start, end): (i64, i64)) -> f64 {
+    quantity * 3600.0 / (end - start).max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(rates: &[f64]) -> SentinelStats {
+        let mut stats = SentinelStats::default();
+        for &rate in rates {
+            stats.observe(rate);
+        }
+        stats
+    }
+
+    #[test]
+    fn readings_in_line_with_the_history_pass() {
+        let stats = history(&[10.0, 10.4, 9.8, 10.1, 9.7, 10.3]);
+        assert!(stats.screen(10.2).is_ok());
+        assert!(stats.screen(10.6).is_ok());
+        assert!(SentinelStats::default().screen(1e6).is_ok(), "no history yet");
+    }
+
+    #[test]
+    fn step_changes_and_outliers_are_flagged() {
+        let stats = history(&[10.0, 10.4, 9.8, 10.1, 9.7, 10.3]);
+        assert!(stats.screen(35.0).unwrap_err().starts_with("Step change"));
+        assert!(stats.screen(3.0).unwrap_err().starts_with("Step change"));
+        assert!(stats.screen(14.0).unwrap_err().contains("σ"));
+        assert!(history(&[10.0, 10.4]).screen(35.0).is_ok(), "still warming up");
+    }
+
+    #[test]
+    fn replayed_and_flat_lined_values_are_flagged() {
+        assert!(history(&[10.0, 12.0, 12.0]).screen(12.0).unwrap_err().starts_with("Duplicate-value run"));
+        assert!(history(&[10.0, 12.0]).screen(12.0).is_ok());
+
+        let stuck = [10.0, 10.0001, 10.0002, 10.0001, 10.0];
+        assert!(history(&stuck).screen(10.0002).unwrap_err().starts_with("Flat-lined sensor"));
+    }
+}
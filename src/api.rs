@@ -1,9 +1,14 @@
 use axum::{
     routing::{get, post},
-    Router, Json, extract::State,
+    Router, Json, extract::{Path, State},
+    http::StatusCode,
 };
 use std::sync::{Arc, Mutex};
 use crate::blockchain::Blockchain;
+use crate::finality::FinalityCertificate;
+use crate::proof::TransactionProof;
+use crate::review::ReviewDecision;
+use crate::transaction::{Authorization, RejectionRecord, Transaction, TransactionKind};
 use tower_http::cors::CorsLayer;
 
 pub struct AppState {
@@ -16,6 +21,15 @@ pub async fn start_api_server(blockchain: Arc<Mutex<Blockchain>>) {
     let app = Router::new()
         .route("/chain", get(get_chain))
         .route("/wallets", get(get_wallets))
+        .route("/wallets/restore", post(restore_wallet_api))
+        .route("/facilities", get(get_facilities))
+        .route("/nonce/:address", get(get_next_nonce))
+        .route("/balance/:address", get(get_balance))
+        .route("/proof/:task_id", get(get_transaction_proof))
+        .route("/rejection/:task_id", get(get_rejection))
+        .route("/finality", get(get_finality))
+        .route("/review", get(get_review_queue))
+        .route("/review/decide", post(decide_review_api))
         .route("/submit", post(submit_task_api))
         .layer(CorsLayer::permissive()) 
         .with_state(state);
@@ -35,11 +49,119 @@ async fn get_wallets(State(state): State<Arc<AppState>>) -> Json<Vec<crate::wall
     Json(wallets)
 }
 
+async fn get_facilities(State(state): State<Arc<AppState>>) -> Json<Vec<crate::facility::Facility>> {
+    Json(state.blockchain.lock().unwrap().facilities.values().cloned().collect())
+}
+
+async fn get_next_nonce(State(state): State<Arc<AppState>>, Path(address): Path<String>) -> Json<u64> {
+    Json(state.blockchain.lock().unwrap().next_nonce(&address))
+}
+
+// Authoritative balances replayed from the ledger (GET /wallets is only the local cache)
+#[derive(serde::Serialize)]
+struct Balance {
+    address: String,
+    balance: u64,   // Settled on-chain balance
+    available: u64, // Minus stakes and burns still pending
+}
+
+async fn get_balance(State(state): State<Arc<AppState>>, Path(address): Path<String>) -> Json<Balance> {
+    let bc = state.blockchain.lock().unwrap();
+    Json(Balance {
+        balance: bc.ledger.balance(&address),
+        available: bc.available_balance(&address),
+        address,
+    })
+}
+
+async fn get_transaction_proof(
+    State(state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TransactionProof>, (StatusCode, Json<String>)> {
+    state.blockchain.lock().unwrap()
+        .prove_transaction(&task_id)
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, Json(format!("No on-chain transaction '{}'", task_id))))
+}
+
+// Why a task was rejected and its stake slashed
+async fn get_rejection(
+    State(state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<RejectionRecord>, (StatusCode, Json<String>)> {
+    state.blockchain.lock().unwrap()
+        .rejections.get(&task_id)
+        .cloned()
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, Json(format!("No rejection recorded for '{}'", task_id))))
+}
+
+// Downstream systems should only act on credits in blocks at or below
+// `finalized_height` (compare with the block index in a /proof response).
+#[derive(serde::Serialize)]
+struct FinalityStatus {
+    finalized_height: u64,
+    finalized_hash: String,
+    certificate: Option<FinalityCertificate>,
+}
+
+async fn get_finality(State(state): State<Arc<AppState>>) -> Json<FinalityStatus> {
+    let bc = state.blockchain.lock().unwrap();
+    let block = bc.finalized_block();
+    Json(FinalityStatus {
+        finalized_height: bc.finalized_height,
+        finalized_hash: block.hash.clone(),
+        certificate: block.certificate.clone(),
+    })
+}
+
+// Packets held back by statistical screening, awaiting a reviewer's decision
+async fn get_review_queue(State(state): State<Arc<AppState>>) -> Json<Vec<Transaction>> {
+    Json(state.blockchain.lock().unwrap().review_queue.clone())
+}
+
+// Signed envelope: ed25519 signature by a reviewer key over
+// ReviewDecision::payload(task, request_hash, approve, reasoning, timestamp),
+// where request_hash is the queued transaction's Transaction::request_hash
+async fn decide_review_api(
+    State(state): State<Arc<AppState>>,
+    Json(decision): Json<ReviewDecision>,
+) -> Result<Json<Transaction>, (StatusCode, Json<String>)> {
+    state.blockchain.lock().unwrap()
+        .review_task(decision)
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))
+}
+
+#[derive(serde::Deserialize)]
+struct RestoreRequest {
+    mnemonic: String,
+}
+
+async fn restore_wallet_api(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<RestoreRequest>,
+) -> Result<Json<crate::wallet::Wallet>, (StatusCode, Json<String>)> {
+    let mut bc = state.blockchain.lock().unwrap();
+    bc.restore_wallet(&payload.mnemonic)
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))
+}
+
 #[derive(serde::Deserialize)]
 struct SubmitRequest {
     wallet: String,
-    task_name: String,
-    metadata: String,
+    task_name: String, // Must be Transaction::task_id(type, wallet, nonce)
+    #[serde(default)]
+    kind: Option<TransactionKind>,
+    #[serde(default)]
+    metadata: Option<String>, // Legacy: the claim as a JSON string
+
+    // Signed envelope: ed25519 signature by the wallet key over
+    // Transaction::signing_payload() (nonce from GET /nonce/:address)
+    public_key: String,
+    nonce: u64,
+    signature: String,
 }
 
 async fn submit_task_api(
@@ -47,11 +169,26 @@ async fn submit_task_api(
     Json(payload): Json<SubmitRequest>,
 ) -> Json<String> {
     let mut bc = state.blockchain.lock().unwrap();
+
+    let kind = match (payload.kind, payload.metadata) {
+        (Some(kind), _) => kind,
+        (None, Some(metadata)) => match serde_json::from_str(&metadata) {
+            Ok(kind) => kind,
+            Err(e) => return Json(format!("Submission failed: invalid metadata ({})", e)),
+        },
+        (None, None) => return Json("Submission failed: missing claim".to_string()),
+    };
+    let mut tx = Transaction::new(payload.wallet, payload.task_name, kind);
+    tx.nonce = payload.nonce;
+    tx.authorization = Some(Authorization {
+        public_key: payload.public_key,
+        signature: payload.signature,
+    });
     
     // FIX: Changed function name to match the new Industrial logic
-    if let Some(_) = bc.submit_industrial_task(&payload.wallet, payload.task_name, payload.metadata) {
+    if bc.submit_industrial_task(tx).is_some() {
         Json("Task Submitted successfully".to_string())
     } else {
         Json("Submission failed".to_string())
     }
-}
\ No newline at end of file
+}
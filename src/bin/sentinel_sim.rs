@@ -0,0 +1,224 @@
+//! Sentinel simulator: a reproducible load source of signed industrial telemetry.
+//!
+//! Holds a device keypair, generates carbon-capture, wastewater or methane readings with
+//! configurable rate and noise, signs them like the Secure Element would, and
+//! submits them (signed by the operator wallet) through the node's HTTP API.
+//!
+//! Usage:
+//!   YUKI_SIM_MNEMONIC="<24 words>" cargo run --bin sentinel_sim -- \
+//!       --type carbon_capture --mean 12.5 --noise 0.1 --interval-ms 500 --count 20 --seed 7
+//!
+//! Options (all optional except the wallet mnemonic):
+//!   --api <url>            Node API (default http://localhost:3030)
+//!   --sentinel-id <id>     Registered device ID (default yuki-industrial-01)
+//!   --device-seed <hex>    32-byte device secret; default is the simulated dev key
+//!                          (only trusted by nodes started with YUKI_DEV_SENTINELS=1)
+//!   --mnemonic <words>     Operator wallet mnemonic (or YUKI_SIM_MNEMONIC)
+//!   --type <task>          carbon_capture | wastewater_treatment | methane_destruction
+//!   --mean <value>         Mean reading (tons, liters or Nm3 CH4 per packet)
+//!   --efficiency <ratio>   Methane destruction efficiency (default 0.98)
+//!   --window-secs <s>      Measurement period each packet covers (default 3600).
+//!                          Periods are back-to-back and end now, so count * window
+//!                          must stay within the node's 24h telemetry horizon.
+//!   --noise <ratio>        Relative standard deviation of the readings (default 0.1)
+//!   --interval-ms <ms>     Delay between packets (default 1000)
+//!   --count <n>            Packets to send (default 10)
+//!   --lat <deg> --lon <deg> GPS fix to report with every packet (default: none)
+//!   --seed <n>             RNG seed for reproducible streams (default 42)
+//!   --spike-at <n>         Multiply packet n's reading (1-based) by 5, to exercise
+//!                          the node's anomaly screening
+//!   --print-key            Print the device public key (for registration) and exit
+
+use bip39::Mnemonic;
+use ed25519_dalek::SigningKey;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::json;
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::Duration;
+use yuki_blockchain::crypto::{address_from_public_key, signing_key_from_mnemonic};
+use yuki_blockchain::facility::GeoPoint;
+use yuki_blockchain::sentinel;
+use yuki_blockchain::transaction::{CarbonCaptureClaim, MethaneClaim, Transaction, TransactionKind, WastewaterClaim};
+
+struct SimConfig {
+    api: String,
+    sentinel_id: String,
+    device_key: SigningKey,
+    mnemonic: Option<String>,
+    task_type: String,
+    mean: f64,
+    efficiency: f64,
+    window_secs: i64,
+    location: Option<GeoPoint>,
+    noise: f64,
+    interval_ms: u64,
+    count: u64,
+    seed: u64,
+    spike_at: Option<u64>,
+    print_key: bool,
+}
+
+impl SimConfig {
+    fn from_args() -> Result<Self, String> {
+        let mut flags: HashMap<String, String> = HashMap::new();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let name = arg.strip_prefix("--").ok_or(format!("Unexpected argument '{}'", arg))?;
+            if name == "print-key" {
+                flags.insert(name.to_string(), String::new());
+            } else {
+                let value = args.next().ok_or(format!("Missing value for --{}", name))?;
+                flags.insert(name.to_string(), value);
+            }
+        }
+
+        let sentinel_id = flags.get("sentinel-id").cloned().unwrap_or("yuki-industrial-01".to_string());
+        let device_key = match flags.get("device-seed") {
+            Some(seed) => {
+                let bytes: [u8; 32] = hex::decode(seed).ok()
+                    .and_then(|b| b.try_into().ok())
+                    .ok_or("--device-seed must be 32 bytes of hex")?;
+                SigningKey::from_bytes(&bytes)
+            }
+            None => sentinel::simulated_device_key(&sentinel_id),
+        };
+
+        let task_type = flags.get("type").cloned().unwrap_or("carbon_capture".to_string());
+        let default_mean = match task_type.as_str() {
+            "carbon_capture" => 10.0,           // tons per packet
+            "wastewater_treatment" => 50_000.0, // liters per packet
+            "methane_destruction" => 800.0,     // Nm3 CH4 per packet
+            other => return Err(format!("Unsupported telemetry type '{}'", other)),
+        };
+
+        Ok(Self {
+            api: flags.get("api").cloned().unwrap_or("http://localhost:3030".to_string()),
+            sentinel_id,
+            device_key,
+            mnemonic: flags.get("mnemonic").cloned().or_else(|| std::env::var("YUKI_SIM_MNEMONIC").ok()),
+            task_type,
+            mean: parse_flag(&flags, "mean", default_mean)?,
+            efficiency: parse_flag(&flags, "efficiency", 0.98)?,
+            window_secs: parse_flag(&flags, "window-secs", 3600)?,
+            location: match (flags.get("lat"), flags.get("lon")) {
+                (Some(_), Some(_)) => Some(GeoPoint { lat: parse_flag(&flags, "lat", 0.0)?, lon: parse_flag(&flags, "lon", 0.0)? }),
+                (None, None) => None,
+                _ => return Err("--lat and --lon must be given together".to_string()),
+            },
+            noise: parse_flag(&flags, "noise", 0.1)?,
+            interval_ms: parse_flag(&flags, "interval-ms", 1000)?,
+            count: parse_flag(&flags, "count", 10)?,
+            seed: parse_flag(&flags, "seed", 42)?,
+            spike_at: flags.contains_key("spike-at").then(|| parse_flag(&flags, "spike-at", 0)).transpose()?,
+            print_key: flags.contains_key("print-key"),
+        })
+    }
+}
+
+fn parse_flag<T: std::str::FromStr>(flags: &HashMap<String, String>, name: &str, default: T) -> Result<T, String> {
+    match flags.get(name) {
+        Some(value) => value.parse().map_err(|_| format!("Invalid value for --{}: '{}'", name, value)),
+        None => Ok(default),
+    }
+}
+
+/// Normally distributed reading (Box-Muller), clamped at zero.
+fn noisy_reading(rng: &mut StdRng, mean: f64, noise: f64) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+    (mean * (1.0 + noise * z)).max(0.0)
+}
+
+fn telemetry_packet(config: &SimConfig, rng: &mut StdRng, (start, end): (i64, i64), spike: bool) -> TransactionKind {
+    let reading = noisy_reading(rng, config.mean, config.noise) * if spike { 5.0 } else { 1.0 };
+    let packet_id = format!("{:016x}", rng.gen::<u64>());
+
+    let mut packet = match config.task_type.as_str() {
+        "carbon_capture" => TransactionKind::CarbonCaptureClaim(CarbonCaptureClaim {
+            sentinel_id: config.sentinel_id.clone(),
+            tons_captured: (reading * 1000.0).round() / 1000.0,
+            measurement_start: Some(start),
+            measurement_end: Some(end),
+            location: config.location,
+            packet_id: Some(packet_id.clone()),
+            hardware_signature: String::new(),
+        }),
+        "methane_destruction" => TransactionKind::MethaneClaim(MethaneClaim {
+            sentinel_id: config.sentinel_id.clone(),
+            ch4_flow_nm3: (reading * 10.0).round() / 10.0,
+            destruction_efficiency: config.efficiency,
+            measurement_start: Some(start),
+            measurement_end: Some(end),
+            location: config.location,
+            packet_id: Some(packet_id.clone()),
+            hardware_signature: String::new(),
+        }),
+        _ => TransactionKind::WastewaterClaim(WastewaterClaim {
+            sentinel_id: config.sentinel_id.clone(),
+            liters_treated: reading.round() as u64,
+            measurement_start: Some(start),
+            measurement_end: Some(end),
+            location: config.location,
+            packet_id: Some(packet_id.clone()),
+            hardware_signature: String::new(),
+        }),
+    };
+    sentinel::sign_telemetry(&config.device_key, &mut packet);
+    packet
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let config = SimConfig::from_args()?;
+    let device_public = hex::encode(config.device_key.verifying_key().as_bytes());
+
+    if config.print_key {
+        println!("{} {}", config.sentinel_id, device_public);
+        return Ok(());
+    }
+
+    let phrase = config.mnemonic.as_deref().ok_or("Operator wallet mnemonic required (--mnemonic or YUKI_SIM_MNEMONIC)")?;
+    let mnemonic = Mnemonic::parse_normalized(phrase.trim()).map_err(|e| format!("Invalid mnemonic: {}", e))?;
+    let wallet_key = signing_key_from_mnemonic(&mnemonic);
+    let wallet = address_from_public_key(&wallet_key.verifying_key());
+
+    println!("🛰️  Sentinel '{}' (device key {})", config.sentinel_id, device_public);
+    println!("🏭 Operator wallet {} -> {}", wallet, config.api);
+
+    let client = reqwest::Client::new();
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut nonce: u64 = client.get(format!("{}/nonce/{}", config.api, wallet)).send().await?.json().await?;
+
+    // Replay a back-to-back measurement history ending now
+    let first_start = chrono::Utc::now().timestamp() - config.window_secs * config.count as i64;
+    for seq in 0..config.count {
+        let start = first_start + config.window_secs * seq as i64;
+        let packet = telemetry_packet(&config, &mut rng, (start, start + config.window_secs), config.spike_at == Some(seq + 1));
+        let task_name = Transaction::task_id(packet.name(), &wallet, nonce);
+
+        let mut tx = Transaction::new(wallet.clone(), task_name.clone(), packet);
+        tx.sign(&wallet_key, nonce);
+        let auth = tx.authorization.clone().expect("transaction was just signed");
+
+        let response: String = client
+            .post(format!("{}/submit", config.api))
+            .json(&json!({
+                "wallet": wallet,
+                "task_name": task_name,
+                "kind": tx.kind,
+                "public_key": auth.public_key,
+                "nonce": nonce,
+                "signature": auth.signature,
+            }))
+            .send().await?
+            .json().await?;
+        println!("[{}/{}] {} -> {}", seq + 1, config.count, tx.kind.canonical_json(), response);
+
+        nonce += 1;
+        tokio::time::sleep(Duration::from_millis(config.interval_ms)).await;
+    }
+    Ok(())
+}
@@ -1,29 +1,107 @@
-use crate::transaction::{Transaction, TaskStatus};
+use crate::transaction::{RejectionCode, RejectionRecord, Transaction, TaskStatus, TransactionKind};
+use crate::verdict::ValidationVerdict;
 use crate::wallet::{Wallet, WalletManager};
 use crate::marketplace::Marketplace;
-use crate::utils::hash_data;
+use crate::consensus;
+use crate::corroboration::CorroborationPolicy;
+use crate::crypto::{self, CanonicalEncoder};
+use crate::facility::{self, Facility};
+use crate::finality::{self, FinalityCertificate, Vote, VoteType};
+use crate::ledger::{self, LedgerState};
+use crate::merkle;
+use crate::methodology::{Gwp, Methodology, MethodologyRegistry, PhysicalLimits};
+use crate::proof::TransactionProof;
+use crate::review::{self, ReviewDecision};
+use crate::sentinel::{self, TelemetryRecord};
+use crate::anomaly::{self, SentinelStats};
+use crate::utils::hash_bytes;
 use chrono::Utc;
 use serde::{Serialize, Deserialize};
-use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 
 const CHAIN_FILE: &str = "chain.json";
+const MAX_CLOCK_DRIFT_SECS: i64 = 120; // Tolerated future skew for network blocks
+const MAX_REORG_DEPTH: usize = 64;      // Side blocks older than this are pruned
+const MAX_ORPHAN_BLOCKS: usize = 32;    // Side blocks kept while their ancestors are missing
+const TELEMETRY_HISTORY_SECS: i64 = 86_400; // Longest rate-limit window; older telemetry is refused
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Block {
+pub const BLOCK_VERSION: u32 = 1;
+
+/// Everything a light client needs to check inclusion proofs; the block hash
+/// is the SHA256 of the header's canonical binary encoding.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BlockHeader {
+    pub version: u32,
     pub index: u64,
     pub timestamp: i64,
-    pub transactions: Vec<Transaction>,
     pub previous_hash: String,
+    pub transactions_root: String, // Merkle root over Transaction::canonical_bytes
+    pub state_root: String,        // Commitment to the ledger state after this block
+    pub producer: String,          // Hex public key of the validator that sealed the block
+}
+
+impl BlockHeader {
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        CanonicalEncoder::new("yuki-block-header-v1")
+            .u32(self.version)
+            .u64(self.index)
+            .i64(self.timestamp)
+            .str(&self.previous_hash)
+            .str(&self.transactions_root)
+            .str(&self.state_root)
+            .str(&self.producer)
+            .finish()
+    }
+
+    pub fn hash(&self) -> String {
+        hash_bytes(&self.canonical_bytes())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Block {
+    pub header: BlockHeader,
+    pub transactions: Vec<Transaction>,
     pub hash: String,
+    #[serde(default)]
+    pub signature: String, // Producer's seal over the block hash (empty for genesis)
+    #[serde(default)]
+    pub certificate: Option<FinalityCertificate>, // Validator precommits, once finalized
 }
 
 impl Block {
-    pub fn new(index: u64, transactions: Vec<Transaction>, previous_hash: String) -> Self {
-        let timestamp = Utc::now().timestamp();
-        let hash = hash_data(&format!("{}{}{:?}{}", index, timestamp, transactions, previous_hash));
-        Self { index, timestamp, transactions, previous_hash, hash }
+    pub fn new(index: u64, timestamp: i64, transactions: Vec<Transaction>, previous_hash: String, state_root: String, producer: String) -> Self {
+        let header = BlockHeader {
+            version: BLOCK_VERSION,
+            index,
+            timestamp,
+            previous_hash,
+            transactions_root: Self::transactions_root(&transactions),
+            state_root,
+            producer,
+        };
+        let hash = header.hash();
+        Self { header, transactions, hash, signature: String::new(), certificate: None }
+    }
+
+    pub fn transactions_root(transactions: &[Transaction]) -> String {
+        hex::encode(merkle::merkle_root(&Self::transaction_leaves(transactions)))
+    }
+
+    fn transaction_leaves(transactions: &[Transaction]) -> Vec<[u8; 32]> {
+        transactions.iter()
+            .map(|tx| merkle::leaf_hash(&tx.canonical_bytes()))
+            .collect()
+    }
+
+    pub fn calculate_hash(&self) -> String {
+        self.header.hash()
+    }
+
+    /// The wallet-signed claims and permits (everything else is settlement).
+    pub fn requests(&self) -> impl Iterator<Item = &Transaction> {
+        self.transactions.iter().filter(|tx| tx.kind.is_request())
     }
 }
 
@@ -31,7 +109,10 @@ impl Block {
 pub enum NetworkMessage {
     Block(Block),
     Transaction(Transaction),
-    ValidationResult(String, TaskStatus),
+    ValidationResult(ValidationVerdict),
+    Vote(Vote),
+    Finality(FinalityCertificate),
+    Review(ReviewDecision),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -41,44 +122,359 @@ pub struct Blockchain {
     pub wallets: WalletManager,
     #[serde(skip)]
     pub marketplace: Marketplace,
+    #[serde(skip)]
+    pub methodologies: MethodologyRegistry, // Crediting rules per restoration activity
     
     pub stake_amount: u64,
+    pub methane_gwp: Gwp, // CO2e horizon of methane rewards, fixed at genesis
     pub tasks_for_validation: Vec<Transaction>,
     pub tasks_for_mining: Vec<Transaction>,
+    #[serde(default)]
+    pub review_queue: Vec<Transaction>, // Anomalous packets awaiting a reviewer's decision
     
     // INDUSTRIAL SECURITY
-    pub authorized_sentinels: HashSet<String>, // Whitelist of Factory IoT Nodes
+    pub authorized_sentinels: HashMap<String, String>, // Factory IoT Node ID -> ed25519 Public Key (hex)
+    #[serde(default)]
+    pub facilities: HashMap<String, Facility>,         // Facility ID -> site, operator wallet, equipment, sentinels
+    #[serde(default)]
+    pub corroboration: CorroborationPolicy,            // M-of-N sentinel rule for high-value claims
     pub used_signatures: HashSet<String>,      // Anti-Replay Database
+    #[serde(default)]
+    pub rejections: HashMap<String, RejectionRecord>, // Why each rejected task was slashed
+    #[serde(default)]
+    pub sentinel_history: HashMap<String, Vec<TelemetryRecord>>, // Accepted measurements per sentinel (rate limits)
+    #[serde(default)]
+    pub sentinel_stats: HashMap<String, SentinelStats>,         // Rolling statistics per sentinel (anomaly screening)
+    #[serde(default)]
+    pub account_nonces: HashMap<String, u64>,  // Last accepted nonce per wallet (Tx Anti-Replay)
+
+    // LEDGER STATE (balances are derived from blocks, never stored directly)
+    #[serde(default)]
+    pub genesis_allocations: BTreeMap<String, u64>, // Committed to by the genesis state root
+    #[serde(skip)]
+    pub ledger: LedgerState,                        // Account state at the canonical tip
+
+    // PROOF-OF-AUTHORITY
+    #[serde(default)]
+    pub validators: Vec<String>, // Authorized block producers (hex ed25519 public keys)
+    #[serde(default)]
+    pub reviewers: Vec<String>,  // Authorized compliance reviewers (hex ed25519 public keys)
+    #[serde(default)]
+    pub block_tree: HashMap<String, Block>, // Known non-canonical blocks (competing branches)
+    #[serde(skip)]
+    pub mined_requests: HashSet<(String, u64)>, // (sender, nonce) of every settled canonical request
+
+    // BFT FINALITY
+    #[serde(default)]
+    pub finalized_height: u64,                  // Highest block covered by a finality certificate
+    #[serde(default)]
+    pub cast_votes: HashSet<(VoteType, u64)>,   // Our own votes (never vote twice at a height)
+    #[serde(default)]
+    pub precommit_locks: BTreeMap<u64, String>, // Height -> block we precommitted (never reorganized away)
+    #[serde(skip)]
+    pub vote_pool: Vec<Vote>,                   // Verified votes above the finalized height
+    #[serde(skip)]
+    pub producer: String,        // Local validator wallet address (keystore) used to seal blocks
 }
 
 impl Blockchain {
+    #[allow(clippy::new_without_default)] // Loads chain.json from disk; not a plain default
     pub fn new() -> Self {
-        // Load or Genesis...
+        // Load (and fully re-verify) or Genesis...
         if let Ok(data) = fs::read_to_string(CHAIN_FILE) {
-            if let Ok(mut loaded_chain) = serde_json::from_str::<Blockchain>(&data) {
-                loaded_chain.wallets = WalletManager::new();
-                loaded_chain.marketplace = Marketplace::new();
-                println!("🏭 Industrial Ledger Loaded.");
-                return loaded_chain;
+            match serde_json::from_str::<Blockchain>(&data) {
+                Ok(mut loaded_chain) => {
+                    loaded_chain.methodologies = MethodologyRegistry::with_methane_gwp(loaded_chain.methane_gwp); // Rewards are re-derived while validating
+                    if Gwp::from_env() != loaded_chain.methane_gwp {
+                        println!("⚠️ Ignoring YUKI_METHANE_GWP: this ledger credits methane at {:?}.", loaded_chain.methane_gwp);
+                    }
+                    match loaded_chain.validate_chain() {
+                        Ok(ledger) => {
+                            if loaded_chain.validators.is_empty() {
+                                loaded_chain.validators = consensus::load_validator_set();
+                            }
+                            if loaded_chain.reviewers.is_empty() {
+                                loaded_chain.reviewers = review::load_reviewer_set();
+                            }
+                            loaded_chain.ledger = ledger;
+                            loaded_chain.mined_requests = Self::request_keys(&loaded_chain.chain);
+                            loaded_chain.revoke_simulated_sentinels();
+                            loaded_chain.wallets = WalletManager::new();
+                            loaded_chain.marketplace = Marketplace::new();
+                            println!("🏭 Industrial Ledger Loaded ({} blocks verified).", loaded_chain.chain.len());
+                            loaded_chain.verify_wallet_cache();
+                            return loaded_chain;
+                        }
+                        Err(e) => Self::quarantine_chain_file(&e),
+                    }
+                }
+                Err(e) => Self::quarantine_chain_file(&format!("Unreadable ledger ({})", e)),
             }
         }
 
-        let genesis_block = Block::new(0, vec![], "0".to_string());
+        let genesis_allocations = ledger::load_genesis_allocations();
+        let ledger = LedgerState::genesis(&genesis_allocations);
+        let genesis_block = Block::new(0, Utc::now().timestamp(), vec![], "0".to_string(), ledger.root(), String::new());
         
-        let mut authorized_sentinels = HashSet::new();
-        // Pre-approve a "Factory Sentinel" for testing
-        authorized_sentinels.insert("yuki-industrial-01".to_string());
+        let mut authorized_sentinels = HashMap::new();
+        // Development nodes pre-approve the console's "Factory Sentinel" (simulated device key)
+        if sentinel::simulated_keys_allowed() {
+            let test_key = sentinel::simulated_device_key("yuki-industrial-01");
+            authorized_sentinels.insert("yuki-industrial-01".to_string(), hex::encode(test_key.verifying_key().as_bytes()));
+        }
         
-        Self {
+        let methane_gwp = Gwp::from_env();
+        let mut blockchain = Self {
             chain: vec![genesis_block],
             wallets: WalletManager::new(),
             marketplace: Marketplace::new(),
+            methodologies: MethodologyRegistry::with_methane_gwp(methane_gwp),
             stake_amount: 500, // Higher stake for Corporations
+            methane_gwp,
             tasks_for_validation: Vec::new(),
             tasks_for_mining: Vec::new(),
+            review_queue: Vec::new(),
             authorized_sentinels,
+            facilities: facility::load_facilities().into_iter().map(|f| (f.id.clone(), f)).collect(),
+            corroboration: CorroborationPolicy::default(),
             used_signatures: HashSet::new(),
+            rejections: HashMap::new(),
+            sentinel_history: HashMap::new(),
+            sentinel_stats: HashMap::new(),
+            account_nonces: HashMap::new(),
+            genesis_allocations,
+            ledger,
+            validators: consensus::load_validator_set(),
+            reviewers: review::load_reviewer_set(),
+            block_tree: HashMap::new(),
+            mined_requests: HashSet::new(),
+            finalized_height: 0,
+            cast_votes: HashSet::new(),
+            precommit_locks: BTreeMap::new(),
+            vote_pool: Vec::new(),
+            producer: String::new(),
+        };
+        blockchain.verify_wallet_cache();
+        blockchain
+    }
+
+    // A tampered or unreadable ledger is never trusted: move it aside for
+    // forensics and start from genesis.
+    fn quarantine_chain_file(reason: &str) {
+        let quarantined = format!("{}.quarantined-{}", CHAIN_FILE, Utc::now().timestamp());
+        println!("🚨 LEDGER INTEGRITY FAILURE: {}", reason);
+        match fs::rename(CHAIN_FILE, &quarantined) {
+            Ok(()) => println!("   Ledger quarantined to '{}'. Starting from genesis.", quarantined),
+            Err(e) => panic!("Refusing to start: cannot quarantine tampered ledger ({})", e),
+        }
+    }
+
+    // --- CHAIN INTEGRITY ---
+    /// Check a block against its parent: recomputed header hash and Merkle
+    /// root, index continuity, linkage, timestamp monotonicity, the producer's
+    /// authority and seal, every request signature and reviewer decision, and
+    /// that the balance movements are exactly the settlement of those requests
+    /// against `state`, the ledger after `previous`. Returns the ledger after
+    /// the block.
+    pub fn validate_block(&self, block: &Block, previous: &Block, state: &LedgerState) -> Result<LedgerState, String> {
+        let header = &block.header;
+        if header.version != BLOCK_VERSION {
+            return Err(format!("Unsupported block version {}", header.version));
+        }
+        if header.transactions_root != Block::transactions_root(&block.transactions) {
+            return Err("Transactions do not match the header Merkle root".to_string());
+        }
+        if block.hash != block.calculate_hash() {
+            return Err("Hash does not match block header".to_string());
         }
+        if header.index != previous.header.index + 1 {
+            return Err(format!("Index {} does not follow {}", header.index, previous.header.index));
+        }
+        if header.previous_hash != previous.hash {
+            return Err("Previous hash does not link to parent block".to_string());
+        }
+        if header.timestamp < previous.header.timestamp {
+            return Err("Timestamp precedes parent block".to_string());
+        }
+        self.validate_producer(block, previous)?;
+        let mut state = state.clone();
+        let mut expected = Vec::new();
+        for request in block.requests() {
+            request.verify_signature().and_then(|_| Self::check_task_id(request)).map_err(|e| format!("Transaction {}: {}", request.task, e))?;
+            if let Some(decision) = &request.review {
+                decision.verify(&self.reviewers)
+                    .and_then(|_| if decision.is_for(request) && decision.status() == request.status { Ok(()) } else { Err("Review does not match the transaction".to_string()) })
+                    .map_err(|e| format!("Transaction {}: {}", request.task, e))?;
+            }
+            let settlement = ledger::settle(request, self.stake_amount, &self.methodologies, &state)
+                .and_then(|txs| state.apply_transactions(&txs).map(|_| txs))
+                .map_err(|e| format!("Transaction {}: {}", request.task, e))?;
+            expected.extend(settlement);
+        }
+        if expected.iter().map(Transaction::canonical_bytes).ne(block.transactions.iter().map(Transaction::canonical_bytes)) {
+            return Err("Balance movements do not match the block's requests".to_string());
+        }
+        Ok(state)
+    }
+
+    // Proof-of-Authority: the producer must be an authorized validator, own
+    // the block's slot, not reuse the parent's slot, and have sealed the block.
+    fn validate_producer(&self, block: &Block, previous: &Block) -> Result<(), String> {
+        self.validate_seal(block)?;
+        let slot = consensus::slot_at(block.header.timestamp);
+        if slot <= consensus::slot_at(previous.header.timestamp) {
+            return Err("Block reuses its parent's slot".to_string());
+        }
+        if consensus::slot_leader(&self.validators, slot) != Some(&block.header.producer) {
+            return Err(format!("Out-of-turn producer for slot {}", slot));
+        }
+        Ok(())
+    }
+
+    // The checks that need no parent: an authorized producer's seal and a
+    // valid certificate, if any.
+    fn validate_seal(&self, block: &Block) -> Result<(), String> {
+        let producer = &block.header.producer;
+        if !self.validators.contains(producer) {
+            return Err("Producer is not an authorized validator".to_string());
+        }
+        crypto::verify(producer, &consensus::seal_payload(&block.hash), &block.signature)
+            .map_err(|e| format!("Invalid producer seal ({})", e))?;
+        if let Some(cert) = &block.certificate {
+            if cert.height != block.header.index || cert.block_hash != block.hash {
+                return Err("Finality certificate is for another block".to_string());
+            }
+            cert.verify(&self.validators).map_err(|e| format!("Invalid finality certificate ({})", e))?;
+        }
+        Ok(())
+    }
+
+    /// Re-verify the whole ledger from genesis, replaying the state root.
+    /// Returns the account state at the tip.
+    pub fn validate_chain(&self) -> Result<LedgerState, String> {
+        let genesis = self.chain.first().ok_or("Ledger has no genesis block")?;
+        if genesis.header.index != 0 || genesis.header.previous_hash != "0" || !genesis.transactions.is_empty() {
+            return Err("Malformed genesis block".to_string());
+        }
+        if genesis.hash != genesis.calculate_hash() || genesis.header.transactions_root != Block::transactions_root(&[]) {
+            return Err("Genesis hash does not match its contents".to_string());
+        }
+
+        let mut ledger = LedgerState::genesis(&self.genesis_allocations);
+        if genesis.header.state_root != ledger.root() {
+            return Err("Genesis state root mismatch".to_string());
+        }
+
+        let mut settled = HashSet::new();
+        for pair in self.chain.windows(2) {
+            let (previous, block) = (&pair[0], &pair[1]);
+            ledger = self.validate_block(block, previous, &ledger).map_err(|e| format!("Block {}: {}", block.header.index, e))?;
+            for tx in block.requests() {
+                let key = (tx.sender.clone(), tx.nonce);
+                if Self::is_replay(tx, key, &HashSet::new(), &mut settled) {
+                    return Err(format!("Block {}: Replayed transaction {}", block.header.index, tx.task));
+                }
+            }
+            if block.header.state_root != ledger.root() {
+                return Err(format!("Block {}: State root mismatch", block.header.index));
+            }
+        }
+
+        let certified = self.chain.iter().rev().find(|b| b.certificate.is_some()).map_or(0, |b| b.header.index);
+        if self.finalized_height != certified {
+            return Err(format!("Finalized height {} is not backed by a certificate", self.finalized_height));
+        }
+        Ok(ledger)
+    }
+
+    // --- INCLUSION PROOFS ---
+    /// Merkle inclusion proof for the most recent on-chain transaction with this task id.
+    pub fn prove_transaction(&self, task_id: &str) -> Option<TransactionProof> {
+        self.chain.iter().rev().find_map(|block| {
+            let leaf_index = block.transactions.iter().position(|tx| tx.task == task_id)?;
+            let path = merkle::merkle_proof(&Block::transaction_leaves(&block.transactions), leaf_index)?;
+            Some(TransactionProof {
+                block_hash: block.hash.clone(),
+                header: block.header.clone(),
+                transaction: block.transactions[leaf_index].clone(),
+                leaf_index,
+                path,
+            })
+        })
+    }
+
+    // --- LEDGER STATE ---
+    /// (sender, nonce) of every settled request in `blocks`.
+    fn request_keys(blocks: &[Block]) -> HashSet<(String, u64)> {
+        blocks.iter()
+            .flat_map(|b| b.requests())
+            .filter(|tx| tx.is_settled())
+            .map(|tx| (tx.sender.clone(), tx.nonce))
+            .collect()
+    }
+
+    // A request may be mined pending (escrow) and then settled, but never
+    // after it was settled, either in `mined` or earlier in `settled`
+    // (which records this one if it settles).
+    fn is_replay(tx: &Transaction, key: (String, u64), mined: &HashSet<(String, u64)>, settled: &mut HashSet<(String, u64)>) -> bool {
+        if mined.contains(&key) {
+            return true;
+        }
+        if tx.is_settled() { !settled.insert(key) } else { settled.contains(&key) }
+    }
+
+    /// Account state after replaying `blocks` (a prefix of an already
+    /// validated chain) from the genesis allocations.
+    fn ledger_after(&self, blocks: &[Block]) -> LedgerState {
+        let mut ledger = LedgerState::genesis(&self.genesis_allocations);
+        for block in blocks {
+            let _ = ledger.apply_transactions(&block.transactions);
+        }
+        ledger
+    }
+
+    /// Yuki committed by this account but not yet mined: stakes on claims
+    /// not yet escrowed on-chain and queued emission burns.
+    fn pending_commitments(&self, address: &str) -> u64 {
+        let mut requests = HashSet::new(); // A claim sits in several pools until it is settled
+        self.tasks_for_validation.iter()
+            .chain(&self.tasks_for_mining)
+            .chain(&self.review_queue)
+            .filter(|tx| tx.sender == address && requests.insert(tx.nonce))
+            .map(|tx| match tx.tons_to_emit() {
+                Some(tons) => ledger::emission_cost(tons).unwrap_or(u64::MAX),
+                None if self.ledger.is_escrowed(&tx.sender, tx.nonce) => 0,
+                None => self.stake_amount,
+            })
+            .fold(0, u64::saturating_add)
+    }
+
+    /// Ledger balance minus pending commitments: what the account can still stake or burn.
+    pub fn available_balance(&self, address: &str) -> u64 {
+        self.ledger.balance(address).saturating_sub(self.pending_commitments(address))
+    }
+
+    // wallets.json is only a cache of the ledger: refresh every local wallet
+    // from the replayed state.
+    fn refresh_wallet_cache(&mut self) {
+        for address in self.wallets.get_all_wallets().into_iter().map(|w| w.address) {
+            let balance = self.available_balance(&address);
+            if let Some(wallet) = self.wallets.get_mut_wallet(&address) {
+                wallet.balance_yuki = balance;
+            }
+        }
+        self.wallets.save_wallets();
+    }
+
+    /// Compare the cached balances with the ledger and rebuild the cache.
+    fn verify_wallet_cache(&mut self) {
+        for wallet in self.wallets.get_all_wallets() {
+            let balance = self.available_balance(&wallet.address);
+            if wallet.balance_yuki != balance {
+                println!("⚠️ Wallet cache out of sync for {}: cached {}, ledger {}. Rebuilt.", wallet.address, wallet.balance_yuki, balance);
+            }
+        }
+        self.refresh_wallet_cache();
     }
 
     pub fn save_chain(&self) {
@@ -88,199 +484,1508 @@ impl Blockchain {
     }
 
     // --- INDUSTRIAL REWARD LOGIC ---
-    fn calculate_industrial_credit(&self, metadata_json: &str) -> u64 {
-        let v: Value = match serde_json::from_str(metadata_json) {
-            Ok(val) => val,
-            Err(_) => return 0,
-        };
-
-        match v["type"].as_str() {
-            Some("carbon_capture") => {
-                let tons = v["tons_captured"].as_f64().unwrap_or(0.0);
-                // 1 Ton Captured = 100 Yuki Credits
-                (tons * 100.0) as u64
-            },
-            Some("wastewater_treatment") => {
-                let liters = v["liters_treated"].as_u64().unwrap_or(0);
-                // 1000 Liters = 1 Yuki Credit
-                liters / 1000 
-            },
-            _ => 0,
-        }
+    fn calculate_industrial_credit(&self, claim: &TransactionKind) -> u64 {
+        self.methodologies.for_claim(claim).map_or(0, |m| m.credits(claim))
     }
 
     // --- THE "EARN-TO-EMIT" VALIDATOR ---
-    fn validate_industrial_packet(&self, metadata: &Value) -> (bool, String) {
+    /// Validated or held for review (with the reason), or the rejection.
+    fn validate_industrial_packet(&self, tx: &Transaction) -> Result<(TaskStatus, String), (RejectionCode, String)> {
+        let claim = &tx.kind;
         
         // 1. HARDWARE ORIGIN (Sentinel Check)
-        if let Some(id) = metadata["sentinel_id"].as_str() {
-            if !self.authorized_sentinels.contains(id) {
-                return (false, format!("⚠️ UNAUTHORIZED HARDWARE: Node '{}' is not in the Industrial Registry.", id));
+        let Some(sentinel_id) = claim.sentinel_id() else {
+            return Err((RejectionCode::InvalidPacket, "⚠️ INVALID PACKET: Missing Sentinel ID.".to_string()));
+        };
+        let Some(device_key) = self.authorized_sentinels.get(sentinel_id) else {
+            return Err((RejectionCode::UnauthorizedHardware, format!("⚠️ UNAUTHORIZED HARDWARE: Node '{}' is not in the Industrial Registry.", sentinel_id)));
+        };
+
+        // 1b. FACILITY (Credits only go to the Site's Operator)
+        let facility = self.check_facility(tx)?;
+
+        // 2. HARDWARE ATTESTATION (Device Signature over the Telemetry)
+        if let Err(e) = sentinel::verify_telemetry(device_key, claim) {
+            return Err((RejectionCode::AttestationFailed, format!("🚨 FRAUD ALERT: Hardware attestation failed ({}).", e)));
+        }
+
+        // 2b. GEOFENCE (Device still on Site; a surveyed site requires a position)
+        match claim.location() {
+            Some(position) if !facility.contains(position) => {
+                return Err((RejectionCode::OutsideGeofence, format!(
+                    "🚨 FRAUD ALERT: Sentinel '{}' reported position ({}, {}) outside facility '{}'.",
+                    sentinel_id, position.lat, position.lon, facility.id
+                )));
             }
-        } else {
-            return (false, "⚠️ INVALID PACKET: Missing Sentinel ID.".to_string());
+            None if !facility.geofence.is_empty() => {
+                return Err((RejectionCode::InvalidPacket, format!("⚠️ INVALID PACKET: Missing GPS position for geofenced facility '{}'.", facility.id)));
+            }
+            _ => {}
         }
 
-        // 2. ANTI-REPLAY (The Chlorophyll/Sensor Loop Fix)
-        if let Some(sig) = metadata["hardware_signature"].as_str() {
+        // 3. ANTI-REPLAY (The Chlorophyll/Sensor Loop Fix)
+        if let Some(sig) = claim.hardware_signature().filter(|s| !s.is_empty()) {
             if self.used_signatures.contains(sig) {
-                return (false, "🚨 FRAUD ALERT: Replay Attack. This sensor packet was already used.".to_string());
+                return Err((RejectionCode::ReplayAttack, "🚨 FRAUD ALERT: Replay Attack. This sensor packet was already used.".to_string()));
             }
         } else {
-            return (false, "⚠️ INVALID PACKET: Missing Hardware Signature.".to_string());
+            return Err((RejectionCode::InvalidPacket, "⚠️ INVALID PACKET: Missing Hardware Signature.".to_string()));
+        }
+
+        let (quantity, window) = self.check_measurement(tx, facility)?;
+
+        // 6. STATISTICAL SCREENING (Deviation from the Sentinel's own History)
+        let stats = self.sentinel_stats.get(sentinel_id);
+        if let Some(Err(e)) = stats.map(|s| s.screen(anomaly::hourly_rate(quantity, window))) {
+            return Ok((TaskStatus::UnderReview, format!("🔍 STATISTICAL ANOMALY: {}", e)));
+        }
+
+        Ok((TaskStatus::Validated, "✅ Industrial Compliance Verified.".to_string()))
+    }
+
+    /// The physics checks of validation: the methodology's limits for the
+    /// facility's equipment and the sentinel's rate limits against the
+    /// telemetry accepted so far. Returns the measured quantity and period.
+    fn check_measurement(&self, tx: &Transaction, facility: &Facility) -> Result<(f64, (i64, i64)), (RejectionCode, String)> {
+        let claim = &tx.kind;
+
+        // 4. ANOMALY DETECTION (Industrial Physics)
+        let (methodology, limits) = self.limits_at(claim, facility)?;
+        if let Err(e) = methodology.validate(claim, &limits) {
+            return Err((RejectionCode::PhysicalLimits, format!("⚠️ ANOMALY: {}", e)));
+        }
+
+        // 5. RATE LIMITS (Physics over Time)
+        let Some((start, end)) = claim.measurement_window() else {
+            return Err((RejectionCode::InvalidPacket, "⚠️ INVALID PACKET: Missing measurement start/end timestamps.".to_string()));
+        };
+        let now = Utc::now().timestamp();
+        if end <= start || end > now + MAX_CLOCK_DRIFT_SECS || start < now - TELEMETRY_HISTORY_SECS {
+            return Err((RejectionCode::InvalidPacket, format!("⚠️ INVALID PACKET: Measurement period {}..{} is empty, in the future or older than {}s.", start, end, TELEMETRY_HISTORY_SECS)));
+        }
+        let quantity = methodology.measurement(claim).unwrap_or(0.0);
+        self.check_rate_limits(claim, (start, end), quantity, &limits)?;
+        Ok((quantity, (start, end)))
+    }
+
+    // The methodology for a claim and its limits for the facility's equipment.
+    fn limits_at(&self, claim: &TransactionKind, facility: &Facility) -> Result<(&dyn Methodology, PhysicalLimits), (RejectionCode, String)> {
+        let Some(methodology) = self.methodologies.for_claim(claim) else {
+            return Err((RejectionCode::UnsupportedActivity, format!("⚠️ UNSUPPORTED ACTIVITY: No methodology registered for '{}'.", claim.name())));
+        };
+        let Some(limits) = facility.limits(claim.name(), methodology.limits()) else {
+            return Err((RejectionCode::UnsupportedActivity, format!("⚠️ UNSUPPORTED ACTIVITY: Facility '{}' has no registered equipment for '{}'.", facility.id, claim.name())));
+        };
+        Ok((methodology, limits))
+    }
+
+    // Overlapping periods and the rolling window, against the telemetry
+    // accepted so far from the same sentinel.
+    fn check_rate_limits(&self, claim: &TransactionKind, window: (i64, i64), quantity: f64, limits: &PhysicalLimits) -> Result<(), (RejectionCode, String)> {
+        let history = self.sentinel_history.get(claim.sentinel_id().unwrap_or_default()).map_or(&[][..], |h| h.as_slice());
+        sentinel::check_rate_limits(history, window, quantity, limits)
+            .map_err(|e| (RejectionCode::RateLimit, format!("⚠️ RATE LIMIT: {}", e)))
+    }
+
+    // Rate limits of a packet held for review, against the telemetry
+    // accepted while it waited. Its age is not held against it: a review
+    // may take longer than the history window.
+    fn recheck_rate_limits(&self, tx: &Transaction) -> Result<(), (RejectionCode, String)> {
+        let claim = &tx.kind;
+        let sentinel_id = claim.sentinel_id().unwrap_or_default();
+        let Some(facility) = self.facility_of(sentinel_id) else {
+            return Err((RejectionCode::UnregisteredFacility, format!("⚠️ UNREGISTERED FACILITY: Sentinel '{}' is no longer installed at a registered facility.", sentinel_id)));
+        };
+        let (methodology, limits) = self.limits_at(claim, facility)?;
+        let Some(window) = claim.measurement_window() else {
+            return Err((RejectionCode::InvalidPacket, "⚠️ INVALID PACKET: Missing measurement start/end timestamps.".to_string()));
+        };
+        self.check_rate_limits(claim, window, methodology.measurement(claim).unwrap_or(0.0), &limits)
+    }
+
+    // Remember an accepted measurement for the sentinel's rate limits and
+    // statistics, forgetting periods too old to matter (the latest is always kept).
+    fn record_telemetry(&mut self, tx: &Transaction) {
+        let (Some(id), Some((start, end))) = (tx.kind.sentinel_id(), tx.kind.measurement_window()) else { return };
+        let quantity = self.methodologies.for_claim(&tx.kind).and_then(|m| m.measurement(&tx.kind)).unwrap_or(0.0);
+        let history = self.sentinel_history.entry(id.to_string()).or_default();
+        if history.iter().any(|r| r.task == tx.task) { return; }
+        history.push(TelemetryRecord { task: tx.task.clone(), start, end, quantity });
+        self.sentinel_stats.entry(id.to_string()).or_default().observe(anomaly::hourly_rate(quantity, (start, end)));
+        let latest = history.iter().map(|r| r.end).max().unwrap_or(end);
+        history.retain(|r| r.end > latest - TELEMETRY_HISTORY_SECS);
+    }
+
+    // --- FACILITY REGISTRY ---
+    pub fn facility_of(&self, sentinel_id: &str) -> Option<&Facility> {
+        self.facilities.values().find(|f| f.sentinels.iter().any(|s| s == sentinel_id))
+    }
+
+    /// The claim's facility, provided the sender is the wallet operating it.
+    fn check_facility(&self, tx: &Transaction) -> Result<&Facility, (RejectionCode, String)> {
+        let sentinel_id = tx.kind.sentinel_id().unwrap_or_default();
+        let facility = self.facility_of(sentinel_id).ok_or((
+            RejectionCode::UnregisteredFacility,
+            format!("⚠️ UNREGISTERED FACILITY: Sentinel '{}' is not installed at a registered facility.", sentinel_id),
+        ))?;
+        if facility.operator != tx.sender {
+            return Err((
+                RejectionCode::OperatorMismatch,
+                format!("🚨 FRAUD ALERT: Wallet {} does not operate facility '{}'.", tx.sender, facility.id),
+            ));
+        }
+        Ok(facility)
+    }
+
+    /// Add or replace a facility. Its sentinels are moved from any other facility.
+    pub fn register_facility(&mut self, facility: Facility) -> Result<(), String> {
+        if facility.id.trim().is_empty() || facility.operator.trim().is_empty() {
+            return Err("Facility ID and operator wallet are required".to_string());
+        }
+        if matches!(facility.geofence.len(), 1 | 2) {
+            return Err("A geofence needs at least 3 points".to_string());
+        }
+        for other in self.facilities.values_mut() {
+            other.sentinels.retain(|s| !facility.sentinels.contains(s));
+        }
+        self.facilities.insert(facility.id.clone(), facility);
+        self.save_chain();
+        Ok(())
+    }
+
+    // --- SENTINEL REGISTRY ---
+    /// Authorize a device key, optionally installing it at a registered
+    /// facility (blank: authorized but not yet installed anywhere).
+    pub fn register_sentinel(&mut self, sentinel_id: &str, public_key: &str, facility_id: &str) -> Result<(), String> {
+        let key_bytes: [u8; 32] = hex::decode(public_key.trim()).ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or("Public key must be 32 bytes of hex")?;
+        ed25519_dalek::VerifyingKey::from_bytes(&key_bytes).map_err(|_| "Invalid ed25519 public key")?;
+
+        let sentinel_id = sentinel_id.trim();
+        if sentinel::is_simulated_key(sentinel_id, &hex::encode(key_bytes)) && !sentinel::simulated_keys_allowed() {
+            return Err("Simulated device keys are public; only development nodes (YUKI_DEV_SENTINELS=1) accept them".to_string());
+        }
+        if !facility_id.trim().is_empty() && !self.facilities.contains_key(facility_id.trim()) {
+            return Err(format!("Unknown facility '{}'", facility_id.trim()));
         }
+        self.authorized_sentinels.insert(sentinel_id.to_string(), hex::encode(key_bytes));
+        for facility in self.facilities.values_mut() {
+            facility.sentinels.retain(|s| s != sentinel_id);
+            if facility.id == facility_id.trim() {
+                facility.sentinels.push(sentinel_id.to_string());
+            }
+        }
+        self.save_chain();
+        Ok(())
+    }
 
-        // 3. ANOMALY DETECTION (Industrial Physics)
-        if let Some(task_type) = metadata["type"].as_str() {
-            if task_type == "carbon_capture" {
-                 if let Some(tons) = metadata["tons_captured"].as_f64() {
-                     // Physics check: A single unit typically captures max 50 tons/hour
-                     if tons > 50.0 {
-                         return (false, "⚠️ ANOMALY: Reported capture rate exceeds physical limits of equipment.".to_string());
-                     }
-                 }
+    // Ledgers created by development nodes may still trust simulated keys.
+    fn revoke_simulated_sentinels(&mut self) {
+        if sentinel::simulated_keys_allowed() { return; }
+        self.authorized_sentinels.retain(|id, key| {
+            let simulated = sentinel::is_simulated_key(id, key);
+            if simulated {
+                println!("⚠️ Revoked sentinel '{}': it uses the public simulated device key (set YUKI_DEV_SENTINELS=1 for development).", id);
             }
+            !simulated
+        });
+    }
+
+    // --- ACCOUNT NONCES (Transaction Anti-Replay) ---
+    pub fn next_nonce(&self, address: &str) -> u64 {
+        self.account_nonces.get(address).map_or(1, |last| last + 1)
+    }
+
+    // Nonces must strictly increase per wallet: duplicates and stale
+    // (out-of-order) transactions are rejected.
+    fn check_nonce(&self, tx: &Transaction) -> Result<(), String> {
+        match self.account_nonces.get(&tx.sender) {
+            Some(last) if tx.nonce <= *last => Err(format!("Stale nonce {} (last accepted: {})", tx.nonce, last)),
+            _ => Ok(()),
+        }
+    }
+
+    // Task ids name a request in the API (/proof, /rejection). They are
+    // derived from the sender and nonce, so they are unique and no wallet
+    // can take another's.
+    fn check_task_id(tx: &Transaction) -> Result<(), String> {
+        let expected = Transaction::task_id(tx.kind.name(), &tx.sender, tx.nonce);
+        if tx.task != expected {
+            return Err(format!("Task id '{}' must be '{}'", tx.task, expected));
         }
+        Ok(())
+    }
 
-        (true, "✅ Industrial Compliance Verified.".to_string())
+    fn record_nonce(&mut self, tx: &Transaction) {
+        let last = self.account_nonces.entry(tx.sender.clone()).or_insert(0);
+        *last = (*last).max(tx.nonce);
     }
 
     // --- SUBMIT WORK (EARN) ---
-    pub fn submit_industrial_task(&mut self, wallet_address: &str, task_name: String, proof_metadata: String) -> Option<Transaction> {
-        if let Some(wallet) = self.wallets.get_mut_wallet(wallet_address) {
-            
-            // Stake Check (Corporations stake more)
-            if wallet.balance_yuki < self.stake_amount {
-                println!("❌ INSUFFICIENT COLLATERAL. Operations Halted.");
-                return None;
-            }
-            wallet.balance_yuki -= self.stake_amount;
+    pub fn submit_industrial_task(&mut self, mut transaction: Transaction) -> Option<Transaction> {
+        if let Err(e) = transaction.verify_signature().and_then(|_| self.check_nonce(&transaction)).and_then(|_| Self::check_task_id(&transaction)) {
+            println!("❌ UNAUTHORIZED SUBMISSION: {}", e);
+            return None;
+        }
 
-            let credit = self.calculate_industrial_credit(&proof_metadata);
-            
-            let transaction = Transaction::new(
-                wallet_address.to_string(),
-                "Protocol-Mint".to_string(), 
-                credit,
-                task_name,
-                proof_metadata,
-            );
-            
-            self.tasks_for_validation.push(transaction.clone());
-            self.save_chain();
-            self.wallets.save_wallets();
-            Some(transaction)
-        } else {
-            None
+        if transaction.kind.sentinel_id().is_none() {
+            println!("❌ UNSUPPORTED SUBMISSION: Only sentinel telemetry claims are validated here.");
+            return None;
         }
+        if let Err((_, reason)) = self.check_facility(&transaction) {
+            println!("❌ UNAUTHORIZED SUBMISSION: {}", reason);
+            return None;
+        }
+        let credit = self.calculate_industrial_credit(&transaction.kind);
+
+        // Stake Check (Corporations stake more). The stake is locked on-chain
+        // by the claim's pending record and settled once it is decided.
+        if self.available_balance(&transaction.sender) < self.stake_amount {
+            println!("❌ INSUFFICIENT COLLATERAL. Operations Halted.");
+            return None;
+        }
+
+        transaction.reset_to_submitted();
+        transaction.amount = credit;
+
+        self.record_nonce(&transaction);
+        self.tasks_for_validation.push(transaction.clone());
+        self.tasks_for_mining.push(transaction.clone()); // Escrows the stake
+        self.save_chain();
+        self.refresh_wallet_cache();
+        Some(transaction)
     }
 
     // --- BURN TO EMIT (SPEND) ---
     // This is the ONLY way tokens leave a wallet. No transfers.
-    pub fn request_emission_permit(&mut self, wallet_address: &str, tons_to_emit: u64) -> bool {
-        let cost_per_ton = 100; // 1 Ton Emission costs 100 Yuki (Ratio 1:1 with Capture)
-        let total_cost = tons_to_emit * cost_per_ton;
-
-        if let Some(wallet) = self.wallets.get_mut_wallet(wallet_address) {
-            if wallet.balance_yuki >= total_cost {
-                // BURN THE TOKENS
-                wallet.balance_yuki -= total_cost;
-                println!("🔥 BURN SUCCESSFUL: {} Yuki Incinerated.", total_cost);
-                println!("🏭 PERMIT GRANTED: Authorized to emit {} tons CO2.", tons_to_emit);
-                
-                // In a real device, this would send an MQTT signal to unlock the smokestack valve
-                self.wallets.save_wallets();
-                return true;
-            } else {
-                println!("❌ PERMIT DENIED: Insufficient Impact Credits.");
-                println!("   Required: {} | Available: {}", total_cost, wallet.balance_yuki);
-                println!("   ACTION: Halt Emissions or perform Restoration immediately.");
-                return false;
-            }
+    // The burn is queued for the next block; the credits are committed at once.
+    pub fn request_emission_permit(&mut self, mut permit: Transaction) -> Option<Transaction> {
+        if let Err(e) = permit.verify_signature().and_then(|_| self.check_nonce(&permit)).and_then(|_| Self::check_task_id(&permit)) {
+            println!("❌ PERMIT DENIED: {}", e);
+            return None;
+        }
+
+        let tons_to_emit = permit.tons_to_emit().unwrap_or(0);
+        let Some(total_cost) = ledger::emission_cost(tons_to_emit) else {
+            println!("❌ PERMIT DENIED: {} tons exceeds the credit supply.", tons_to_emit);
+            return None;
+        };
+        let available = self.available_balance(&permit.sender);
+
+        if available < total_cost {
+            println!("❌ PERMIT DENIED: Insufficient Impact Credits.");
+            println!("   Required: {} | Available: {}", total_cost, available);
+            println!("   ACTION: Halt Emissions or perform Restoration immediately.");
+            return None;
         }
-        false
+
+        // BURN THE TOKENS
+        permit.amount = 0; // The cost is derived from the tons, never from the wire
+        permit.reset_to_submitted();
+        self.record_nonce(&permit);
+        self.tasks_for_mining.push(permit.clone());
+        println!("🔥 BURN SUCCESSFUL: {} Yuki Incinerated.", total_cost);
+        println!("🏭 PERMIT GRANTED: Authorized to emit {} tons CO2.", tons_to_emit);
+
+        // In a real device, this would send an MQTT signal to unlock the smokestack valve
+        self.save_chain();
+        self.refresh_wallet_cache();
+        Some(permit)
     }
 
-    pub fn run_automated_validation(&mut self) -> Vec<(String, TaskStatus)> {
+    /// Decide the pending claims. Returns the verdicts to gossip, signed
+    /// with the validator key (none if this node holds no validator key).
+    pub fn run_automated_validation(&mut self) -> Vec<ValidationVerdict> {
+        let validator = self.wallets.local_public_key(&self.producer).unwrap_or_default();
         let mut results = Vec::new();
-        for i in (0..self.tasks_for_validation.len()).rev() {
-            let task = self.tasks_for_validation[i].clone();
-            let mut is_valid = false;
-            let mut reason = "Unknown".to_string();
-
-            if let Ok(v) = serde_json::from_str::<Value>(&task.proof_metadata) {
-                let (passed, msg) = self.validate_industrial_packet(&v);
-                is_valid = passed;
-                reason = msg;
-                
-                // If valid, LOCK the signature forever
-                if passed { 
-                    if let Some(sig) = v["hardware_signature"].as_str() { 
-                        self.used_signatures.insert(sig.to_string()); 
-                    } 
+        // In submission order, so each packet is rate-limited against the ones before it
+        let pending = std::mem::take(&mut self.tasks_for_validation);
+        let mut consumed = HashSet::new(); // Packets already used to corroborate another claim
+        for (i, task) in pending.iter().enumerate() {
+            if consumed.contains(&i) { continue; }
+            let mut task = task.clone();
+            let mut verdict = self.validate_industrial_packet(&task);
+
+            // 7. CORROBORATION (High-Value Claims need M-of-N Sentinels)
+            if matches!(verdict, Ok((TaskStatus::Validated, _))) && self.corroboration.applies_to(task.amount) {
+                let corroborators = self.find_corroborators(&task, &pending, i + 1, &consumed);
+                let confirmations = corroborators.len() + 1;
+                if confirmations < self.corroboration.required {
+                    let end = task.kind.measurement_window().map_or(0, |(_, end)| end);
+                    if Utc::now().timestamp() - end <= self.corroboration.timeout_secs {
+                        println!("\n[SENTINEL] Packet {} awaiting corroboration ({} of {} sentinels).", task.task, confirmations, self.corroboration.required);
+                        self.tasks_for_validation.push(task);
+                        continue;
+                    }
+                    verdict = Err((RejectionCode::Uncorroborated, format!(
+                        "⚠️ UNCORROBORATED: {} Yuki claim confirmed by {} of {} required sentinels within {}s.",
+                        task.amount, confirmations, self.corroboration.required, self.corroboration.timeout_secs
+                    )));
+                }
+                // Only a corroborated claim consumes its confirmations; otherwise they stay pending
+                let corroborators = if verdict.is_ok() { corroborators } else { Vec::new() };
+                for j in corroborators {
+                    consumed.insert(j);
+                    let mut corroborator = pending[j].clone();
+                    if let Some(sig) = corroborator.kind.hardware_signature() {
+                        self.used_signatures.insert(sig.to_string());
+                    }
+                    self.record_telemetry(&corroborator);
+                    println!("\n[SENTINEL] Packet {} CORROBORATES {} (stake released, no mint).", corroborator.task, task.task);
+                    task.corroborated_by.push(corroborator.kind.packet_id().unwrap_or(&corroborator.task).to_string());
+                    corroborator.status = TaskStatus::Validated;
+                    corroborator.amount = 0; // The measurement is credited once, to the claim it confirms
+                    corroborator.corroborates = Some(task.task.clone());
+                    results.extend(self.sign_verdict(&corroborator, None));
+                    self.tasks_for_mining.push(corroborator);
+                }
+            }
+
+            // If valid (or held for review), LOCK the signature forever
+            if verdict.is_ok() {
+                if let Some(sig) = task.kind.hardware_signature() {
+                    self.used_signatures.insert(sig.to_string());
                 }
             }
             
-            if is_valid {
-                println!("\n[SENTINEL] Packet {} APPROVED: {}", task.task, reason);
-                let mut validated_task = self.tasks_for_validation.remove(i);
-                validated_task.status = TaskStatus::Validated;
-                self.tasks_for_mining.push(validated_task);
-                results.push((task.task, TaskStatus::Validated));
-            } else {
-                println!("\n[SENTINEL] Packet {} REJECTED: {}", task.task, reason);
-                let _ = self.tasks_for_validation.remove(i);
-                results.push((task.task, TaskStatus::Rejected));
-                self.wallets.save_wallets(); // Slashing happens here (stake already removed)
+            match verdict {
+                Ok((TaskStatus::UnderReview, reason)) => {
+                    println!("\n[SENTINEL] Packet {} QUARANTINED for review: {}", task.task, reason);
+                    task.status = TaskStatus::UnderReview;
+                    results.extend(self.sign_verdict(&task, None));
+                    self.review_queue.push(task);
+                }
+                Ok((status, reason)) => {
+                    println!("\n[SENTINEL] Packet {} APPROVED: {}", task.task, reason);
+                    task.status = status;
+                    results.extend(self.sign_verdict(&task, None));
+                    self.record_telemetry(&task);
+                    self.tasks_for_mining.push(task);
+                }
+                Err((code, reason)) => {
+                    println!("\n[SENTINEL] Packet {} REJECTED: {}", task.task, reason);
+                    let record = RejectionRecord { task: task.task.clone(), code, message: reason, validator: validator.clone(), timestamp: Utc::now().timestamp() };
+                    self.rejections.insert(task.task.clone(), record.clone());
+                    task.status = TaskStatus::Rejected;
+                    results.extend(self.sign_verdict(&task, Some(record)));
+                    self.tasks_for_mining.push(task); // Mined so the slash is on-chain
+                }
             }
         }
         self.save_chain();
         results
     }
 
+    // Peers only act on verdicts signed by a validator key.
+    fn sign_verdict(&self, claim: &Transaction, rejection: Option<RejectionRecord>) -> Option<ValidationVerdict> {
+        let public_key = self.wallets.local_public_key(&self.producer).filter(|key| self.validators.contains(key))?;
+        let (_, signature) = self.wallets.sign_bytes(&self.producer, &ValidationVerdict::payload(claim, rejection.as_ref()))?;
+        Some(ValidationVerdict { claim: claim.clone(), rejection, validator: public_key, signature })
+    }
+
+    /// Indices of pending packets (from `first` on, not yet consumed) by other
+    /// sentinels at the claimant's facility that report a matching, otherwise
+    /// valid measurement of the same activity; at most one per sentinel.
+    fn find_corroborators(&self, task: &Transaction, pending: &[Transaction], first: usize, consumed: &HashSet<usize>) -> Vec<usize> {
+        let facility_of = |tx: &Transaction| tx.kind.sentinel_id().and_then(|id| self.facility_of(id)).map(|f| &f.id);
+        let reading = |tx: &Transaction| Some((self.methodologies.for_claim(&tx.kind)?.measurement(&tx.kind)?, tx.kind.measurement_window()?));
+        let (Some(facility), Some(claimed)) = (facility_of(task), reading(task)) else { return Vec::new() };
+        let mut sentinels = HashSet::from([task.kind.sentinel_id()]);
+        let mut found = Vec::new();
+        for (j, candidate) in pending.iter().enumerate().skip(first) {
+            if consumed.contains(&j) || candidate.kind.name() != task.kind.name() || sentinels.contains(&candidate.kind.sentinel_id()) || facility_of(candidate) != Some(facility) {
+                continue;
+            }
+            if !reading(candidate).is_some_and(|r| self.corroboration.matches(claimed, r)) {
+                continue;
+            }
+            if !matches!(self.validate_industrial_packet(candidate), Ok((TaskStatus::Validated, _))) {
+                continue;
+            }
+            sentinels.insert(candidate.kind.sentinel_id());
+            found.push(j);
+        }
+        found
+    }
+
+    // --- MANUAL REVIEW ---
+    /// Sign a decision on `request` with a reviewer wallet held in the local keystore.
+    pub fn sign_review(&self, reviewer_address: &str, request: &Transaction, approve: bool, reasoning: &str) -> Option<ReviewDecision> {
+        let timestamp = Utc::now().timestamp();
+        let request_hash = request.request_hash();
+        let payload = ReviewDecision::payload(&request.task, &request_hash, approve, reasoning, timestamp);
+        let (reviewer, signature) = self.wallets.sign_bytes(reviewer_address, &payload)?;
+        Some(ReviewDecision { task: request.task.clone(), request_hash, approve, reasoning: reasoning.to_string(), reviewer, timestamp, signature })
+    }
+
+    /// Apply a reviewer's signed decision to a queued packet: the claim is
+    /// settled (minted or slashed) in the next block with the decision attached.
+    /// Telemetry accepted while the packet waited may now overlap it or use
+    /// up its rate limit, so an approval is checked again first.
+    pub fn review_task(&mut self, decision: ReviewDecision) -> Result<Transaction, String> {
+        decision.verify(&self.reviewers)?;
+        let pos = self.review_queue.iter().position(|t| decision.is_for(t))
+            .ok_or(format!("Task '{}' is not awaiting review", decision.task))?;
+        if decision.approve {
+            self.recheck_rate_limits(&self.review_queue[pos])
+                .map_err(|(_, reason)| format!("Task '{}' can no longer be approved: {} Reject it instead.", decision.task, reason))?;
+        }
+        let mut task = self.review_queue.remove(pos);
+        task.status = decision.status();
+        if decision.approve {
+            self.record_telemetry(&task);
+        } else {
+            self.rejections.insert(task.task.clone(), RejectionRecord {
+                task: task.task.clone(),
+                code: RejectionCode::ReviewerRejected,
+                message: decision.reasoning.clone(),
+                validator: decision.reviewer.clone(),
+                timestamp: decision.timestamp,
+            });
+        }
+        println!("\n[REVIEW] Packet {} {} by reviewer {}: {}", task.task, if decision.approve { "APPROVED" } else { "REJECTED" }, decision.reviewer, decision.reasoning);
+        task.review = Some(decision);
+        self.tasks_for_mining.push(task.clone());
+        self.save_chain();
+        self.refresh_wallet_cache();
+        Ok(task)
+    }
+
     pub fn mine_block(&mut self) -> Option<Block> {
+        self.mine_block_at(Utc::now().timestamp())
+    }
+
+    fn mine_block_at(&mut self, timestamp: i64) -> Option<Block> {
         if self.tasks_for_mining.is_empty() { return None; }
+
+        // PROOF-OF-AUTHORITY: only the slot leader may seal the next block
+        let Some(public_key) = self.wallets.local_public_key(&self.producer) else {
+            println!("❌ Not a validator node: set YUKI_VALIDATOR to an unlocked keystore wallet.");
+            return None;
+        };
+        if !self.validators.contains(&public_key) {
+            println!("❌ Validator {} is not in the authorized producer set.", public_key);
+            return None;
+        }
+        let slot = consensus::slot_at(timestamp);
+        let tip_slot = consensus::slot_at(self.chain.last().unwrap().header.timestamp);
+        if slot <= tip_slot || consensus::slot_leader(&self.validators, slot) != Some(&public_key) {
+            let wait = consensus::seconds_until_turn(&self.validators, &public_key, timestamp).unwrap_or(0);
+            println!("⏳ Not our slot. Next production slot in ~{}s.", wait);
+            return None;
+        }
+
+        // Each request is mined together with its settlement movements; only
+        // include those the ledger can settle (e.g. a burn racing a slash).
+        // A claim already decided skips its pending record and locks its
+        // stake in the settling one.
+        let decided: HashSet<(String, u64)> = self.tasks_for_mining.iter()
+            .filter(|t| t.is_settled())
+            .map(|t| (t.sender.clone(), t.nonce))
+            .collect();
+        let mut state = self.ledger.clone();
         let mut transactions_for_block = Vec::new();
-        while let Some(mut task) = self.tasks_for_mining.pop() {
-            if let Some(wallet) = self.wallets.get_mut_wallet(&task.sender) {
-                wallet.balance_yuki += task.amount + self.stake_amount; // Return Stake + Reward
-                task.receiver = task.sender.clone();
-                transactions_for_block.push(task);
+        while let Some(task) = self.tasks_for_mining.pop() {
+            if !task.is_settled() && decided.contains(&(task.sender.clone(), task.nonce)) {
+                continue;
+            }
+            let mut next = state.clone();
+            match ledger::settle(&task, self.stake_amount, &self.methodologies, &state).and_then(|txs| next.apply_transactions(&txs).map(|_| txs)) {
+                Ok(txs) => { state = next; transactions_for_block.extend(txs); }
+                Err(e) => println!("⚠️ Dropped {} from block: {}", task.task, e),
             }
         }
         if transactions_for_block.is_empty() { return None; }
+        let state_root = state.root();
         let previous_block = self.chain.last().unwrap();
-        let new_block = Block::new(previous_block.index + 1, transactions_for_block, previous_block.hash.clone());
+        let mut new_block = Block::new(previous_block.header.index + 1, timestamp, transactions_for_block, previous_block.hash.clone(), state_root, public_key);
+        let (_, seal) = self.wallets.sign_bytes(&self.producer, &consensus::seal_payload(&new_block.hash))?;
+        new_block.signature = seal;
         println!("✅ New Industrial Block {} mined!", new_block.hash);
+        self.apply_block(&new_block);
         self.chain.push(new_block.clone());
         self.save_chain();
-        self.wallets.save_wallets();
+        self.refresh_wallet_cache();
         Some(new_block)
     }
 
+    // --- BLOCK EFFECTS (applied on connect, reverted on reorg) ---
+    fn apply_block(&mut self, block: &Block) {
+        let _ = self.ledger.apply_transactions(&block.transactions); // Already validated
+        for tx in block.requests() {
+            let mined = |t: &Transaction| t.sender == tx.sender && t.nonce == tx.nonce;
+            if tx.is_settled() {
+                self.tasks_for_mining.retain(|t| !mined(t));
+                self.tasks_for_validation.retain(|t| !mined(t));
+                self.review_queue.retain(|t| !mined(t));
+                self.mined_requests.insert((tx.sender.clone(), tx.nonce));
+            } else {
+                self.tasks_for_mining.retain(|t| !mined(t) || t.is_settled()); // Only the stake is locked; the claim is still undecided
+            }
+            self.record_nonce(tx);
+            if tx.status == TaskStatus::Validated {
+                self.record_telemetry(tx);
+            }
+        }
+    }
+
+    // Orphaned transactions go back to the mining pool so a later block can
+    // include them. The ledger itself is rebuilt by `reorganize`.
+    fn revert_block(&mut self, block: &Block) {
+        self.tasks_for_mining.extend(block.requests().cloned());
+    }
+
+    // --- FORK CHOICE ---
+    // Competing blocks are kept in `block_tree`. The canonical chain is the
+    // longest chain of authorized, fully valid blocks; ties keep the branch
+    // seen first.
+    fn find_block(&self, hash: &str) -> Option<&Block> {
+        self.block_tree.get(hash).or_else(|| self.chain.iter().rev().find(|b| b.hash == hash))
+    }
+
+    // The canonical position of `hash`, if it is the canonical block at `index`.
+    fn canonical_position(&self, index: u64, hash: &str) -> Option<usize> {
+        let position = usize::try_from(index).ok()?;
+        self.chain.get(position).filter(|b| b.hash == hash).map(|_| position)
+    }
+
+    /// Walk from a side block back to the canonical chain. Returns the
+    /// canonical fork point position and the branch (oldest first), or None
+    /// while an ancestor is still missing.
+    fn branch_to(&self, hash: &str) -> Option<(usize, Vec<Block>)> {
+        let mut branch = Vec::new();
+        let mut cursor = self.block_tree.get(hash)?;
+        loop {
+            branch.push(cursor.clone());
+            let parent = &cursor.header.previous_hash;
+            if let Some(fork) = cursor.header.index.checked_sub(1).and_then(|index| self.canonical_position(index, parent)) {
+                branch.reverse();
+                return Some((fork, branch));
+            }
+            cursor = self.block_tree.get(parent)?;
+            if branch.len() > MAX_REORG_DEPTH {
+                return None;
+            }
+        }
+    }
+
+    /// Validate a branch as if it replaced everything after `fork`.
+    fn validate_branch(&self, fork: usize, branch: &[Block]) -> Result<(), String> {
+        if (fork as u64) < self.finalized_height {
+            return Err(format!("Branch reverts finalized block {}", self.finalized_height));
+        }
+        if let Some(locked) = self.chain[fork + 1..].iter().find(|b| self.precommit_locks.get(&b.header.index) == Some(&b.hash)) {
+            return Err(format!("Branch detaches block {} we precommitted", locked.header.index));
+        }
+        // Extending the tip (the common case) starts from the tip state
+        // instead of replaying the chain from genesis
+        let rebuilt;
+        let (mut ledger, mined) = if fork + 1 == self.chain.len() {
+            (self.ledger.clone(), &self.mined_requests)
+        } else {
+            rebuilt = Self::request_keys(&self.chain[..=fork]);
+            (self.ledger_after(&self.chain[..=fork]), &rebuilt)
+        };
+        let mut settled = HashSet::new();
+
+        let mut previous = &self.chain[fork];
+        for block in branch {
+            let index = block.header.index;
+            ledger = self.validate_block(block, previous, &ledger).map_err(|e| format!("Block {}: {}", index, e))?;
+            if block.header.timestamp > Utc::now().timestamp() + MAX_CLOCK_DRIFT_SECS {
+                return Err(format!("Block {}: Timestamp is in the future", index));
+            }
+            for tx in block.requests() {
+                let key = (tx.sender.clone(), tx.nonce);
+                if Self::is_replay(tx, key, mined, &mut settled) {
+                    return Err(format!("Block {}: Replayed transaction {}", index, tx.task));
+                }
+            }
+            if block.header.state_root != ledger.root() {
+                return Err(format!("Block {}: State root mismatch", index));
+            }
+            previous = block;
+        }
+        Ok(())
+    }
+
+    /// Switch to the best valid branch if it is longer than the current chain.
+    fn apply_fork_choice(&mut self) {
+        let mut best: Option<(usize, Vec<Block>)> = None;
+        let mut best_height = self.chain.last().unwrap().header.index;
+
+        let mut candidates: Vec<String> = self.block_tree.keys().cloned().collect();
+        candidates.sort();
+        for hash in candidates {
+            let Some((fork, branch)) = self.branch_to(&hash) else { continue };
+            let height = branch.last().unwrap().header.index;
+            if height <= best_height {
+                continue;
+            }
+            match self.validate_branch(fork, &branch) {
+                Ok(()) => { best_height = height; best = Some((fork, branch)); }
+                Err(e) => {
+                    println!("⚠️ Rejected block {}: {}", height, e);
+                    self.block_tree.remove(&hash);
+                }
+            }
+        }
+
+        if let Some((fork, branch)) = best {
+            self.reorganize(fork, branch);
+        }
+    }
+
+    fn reorganize(&mut self, fork: usize, branch: Vec<Block>) {
+        let detached = self.chain.split_off(fork + 1);
+        for block in detached.iter().rev() {
+            self.revert_block(block);
+        }
+        if !detached.is_empty() {
+            self.ledger = self.ledger_after(&self.chain);
+            self.mined_requests = Self::request_keys(&self.chain);
+        }
+        for block in branch {
+            self.block_tree.remove(&block.hash);
+            self.apply_block(&block);
+            if block.certificate.is_some() {
+                self.finalized_height = block.header.index;
+            }
+            self.chain.push(block);
+        }
+        if !detached.is_empty() {
+            println!("🔀 REORG: {} block(s) replaced; now at height {}.", detached.len(), self.chain.last().unwrap().header.index);
+        }
+        for block in detached {
+            self.block_tree.insert(block.hash.clone(), block);
+        }
+
+        // Forget side blocks too deep (or too old to beat finality) to ever win a fork choice
+        let floor = self.chain.last().unwrap().header.index.saturating_sub(MAX_REORG_DEPTH as u64).max(self.finalized_height);
+        self.block_tree.retain(|_, b| b.header.index > floor);
+    }
+
+    /// Keep at most MAX_ORPHAN_BLOCKS side blocks whose ancestry is still
+    /// missing, preferring the lowest (closest to connecting).
+    fn prune_orphans(&mut self) {
+        let mut orphans: Vec<(u64, String)> = self.block_tree.values()
+            .filter(|b| self.branch_to(&b.hash).is_none())
+            .map(|b| (b.header.index, b.hash.clone()))
+            .collect();
+        orphans.sort();
+        for (_, hash) in orphans.into_iter().skip(MAX_ORPHAN_BLOCKS) {
+            self.block_tree.remove(&hash);
+        }
+    }
+
+    // --- BFT FINALITY ---
+    // Every validator prevotes for the canonical block at each height above
+    // the finalized one, precommits once it sees a prevote quorum for it, and
+    // finalizes on a precommit quorum. A validator votes at most once per
+    // (phase, height) and, like a Tendermint lock, never reorganizes away a
+    // block it precommitted. Any two quorums share an honest validator, which
+    // can only have precommitted blocks on one history, so two conflicting
+    // blocks can never both be finalized (validators locked on different
+    // branches stall instead). Finalizing a later height also finalizes any
+    // height that stalled.
+    pub fn finalized_block(&self) -> &Block {
+        &self.chain[self.finalized_height as usize]
+    }
+
+    fn vote_count(&self, vote_type: VoteType, height: u64, block_hash: &str) -> usize {
+        self.vote_pool.iter()
+            .filter(|v| v.vote_type == vote_type && v.height == height && v.block_hash == block_hash)
+            .map(|v| v.validator.as_str())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    fn cast_vote(&mut self, vote_type: VoteType, height: u64, block_hash: &str) -> Option<Vote> {
+        let public_key = self.wallets.local_public_key(&self.producer)?;
+        if !self.validators.contains(&public_key) || self.cast_votes.contains(&(vote_type, height)) {
+            return None;
+        }
+        let (validator, signature) = self.wallets.sign_bytes(&self.producer, &Vote::payload(vote_type, height, block_hash))?;
+        self.cast_votes.insert((vote_type, height));
+        if vote_type == VoteType::Precommit {
+            self.precommit_locks.insert(height, block_hash.to_string());
+        }
+        let vote = Vote { vote_type, height, block_hash: block_hash.to_string(), validator, signature };
+        self.vote_pool.push(vote.clone());
+        Some(vote)
+    }
+
+    /// Cast whatever votes are now due and finalize on a precommit quorum.
+    /// Returns the messages (our votes, new certificates) to gossip.
+    pub fn advance_finality(&mut self) -> Vec<NetworkMessage> {
+        let mut outgoing = Vec::new();
+        let quorum = finality::quorum(self.validators.len());
+        loop {
+            let before = outgoing.len();
+            let pending: Vec<(u64, String)> = self.chain.iter()
+                .skip(self.finalized_height as usize + 1)
+                .map(|b| (b.header.index, b.hash.clone()))
+                .collect();
+            for (height, hash) in &pending {
+                if let Some(vote) = self.cast_vote(VoteType::Prevote, *height, hash) {
+                    outgoing.push(NetworkMessage::Vote(vote));
+                }
+                if self.vote_count(VoteType::Prevote, *height, hash) >= quorum {
+                    if let Some(vote) = self.cast_vote(VoteType::Precommit, *height, hash) {
+                        outgoing.push(NetworkMessage::Vote(vote));
+                    }
+                }
+            }
+            if let Some((height, hash)) = pending.iter().rev().find(|(h, hash)| self.vote_count(VoteType::Precommit, *h, hash) >= quorum) {
+                let cert = FinalityCertificate {
+                    height: *height,
+                    block_hash: hash.clone(),
+                    precommits: self.vote_pool.iter()
+                        .filter(|v| v.vote_type == VoteType::Precommit && v.height == *height && &v.block_hash == hash)
+                        .cloned()
+                        .collect(),
+                };
+                self.finalize(cert.clone());
+                outgoing.push(NetworkMessage::Finality(cert));
+            }
+            if outgoing.len() == before { break; }
+        }
+        if !outgoing.is_empty() { self.save_chain(); }
+        outgoing
+    }
+
+    fn finalize(&mut self, cert: FinalityCertificate) {
+        let height = cert.height;
+        self.chain[height as usize].certificate = Some(cert);
+        self.finalized_height = height;
+        self.vote_pool.retain(|v| v.height > height);
+        self.cast_votes.retain(|(_, h)| *h > height);
+        self.precommit_locks.retain(|h, _| *h > height);
+        self.block_tree.retain(|_, b| b.header.index > height);
+        println!("🔏 FINALIZED: Block {} is irreversible.", height);
+    }
+
+    pub fn add_vote_from_network(&mut self, vote: Vote) -> Vec<NetworkMessage> {
+        if vote.height <= self.finalized_height || self.vote_pool.contains(&vote) {
+            return Vec::new();
+        }
+        if let Err(e) = vote.verify(&self.validators) {
+            println!("⚠️ Dropped vote for block {}: {}", vote.height, e);
+            return Vec::new();
+        }
+        self.vote_pool.push(vote);
+        self.advance_finality()
+    }
+
+    pub fn add_certificate_from_network(&mut self, cert: FinalityCertificate) {
+        if cert.height <= self.finalized_height { return; }
+        if self.chain.get(cert.height as usize).is_none_or(|b| b.hash != cert.block_hash) {
+            println!("⚠️ Ignored finality certificate for unknown block {}", cert.height);
+            return;
+        }
+        if let Err(e) = cert.verify(&self.validators) {
+            println!("⚠️ Rejected finality certificate for block {}: {}", cert.height, e);
+            return;
+        }
+        self.finalize(cert);
+        self.save_chain();
+    }
+
     // --- Helpers (Network Sync, Wallets) ---
-    pub fn create_wallet(&mut self) -> Wallet { let w = self.wallets.create_wallet(); self.wallets.save_wallets(); w }
+    pub fn create_wallet(&mut self) -> Result<Wallet, String> { let mut w = self.wallets.create_wallet()?; self.refresh_wallet_cache(); w.balance_yuki = self.available_balance(&w.address); Ok(w) }
+    pub fn restore_wallet(&mut self, phrase: &str) -> Result<Wallet, String> { let mut w = self.wallets.restore_from_mnemonic(phrase)?; self.refresh_wallet_cache(); w.balance_yuki = self.available_balance(&w.address); Ok(w) }
     pub fn view_wallets(&self) { self.wallets.view_wallets(); }
-    pub fn add_block_from_network(&mut self, block: Block) { /* Same as before, just update pools */ 
-        let previous_block = self.chain.last().unwrap();
-        if block.previous_hash == previous_block.hash {
-            for tx in &block.transactions {
-                self.tasks_for_mining.retain(|t| t.task != tx.task);
-                self.tasks_for_validation.retain(|t| t.task != tx.task);
+    pub fn add_block_from_network(&mut self, block: Block) {
+        if self.find_block(&block.hash).is_some() { return; }
+        let tip = self.chain.last().unwrap().header.index;
+        let index = block.header.index;
+        // Only sealed blocks from authorized producers, within reach of a fork choice, are kept
+        let admission = if index <= self.finalized_height || index > tip + MAX_REORG_DEPTH as u64 {
+            Err(format!("Height is outside the reorganization window ({}..={})", self.finalized_height + 1, tip + MAX_REORG_DEPTH as u64))
+        } else if block.hash != block.calculate_hash() {
+            Err("Hash does not match block header".to_string())
+        } else {
+            match self.find_block(&block.header.previous_hash) {
+                Some(parent) => self.validate_producer(&block, parent),
+                None => self.validate_seal(&block), // Orphan: the parent has not arrived yet
             }
-            self.chain.push(block);
-            self.save_chain();
-            self.wallets.save_wallets(); 
+        };
+        if let Err(e) = admission {
+            println!("⚠️ Rejected block {}: {}", index, e);
+            return;
+        }
+        self.block_tree.insert(block.hash.clone(), block);
+        self.apply_fork_choice();
+        self.prune_orphans();
+        self.save_chain();
+        self.refresh_wallet_cache();
+    }
+    pub fn add_task_from_network(&mut self, mut tx: Transaction) {
+        if let Err(e) = tx.verify_signature().and_then(|_| self.check_nonce(&tx)).and_then(|_| Self::check_task_id(&tx)) {
+            println!("⚠️ Dropped telemetry {}: {}", tx.task, e);
+            return;
+        }
+        if tx.tons_to_emit().is_none() && tx.kind.sentinel_id().is_none() {
+            println!("⚠️ Dropped {}: {} transactions are not accepted", tx.task, tx.kind.name());
+            return;
+        }
+        // Neither the reward nor the status is covered by the sender's signature: never trust the peer's values
+        tx.reset_to_submitted();
+        tx.amount = self.calculate_industrial_credit(&tx.kind);
+        let Some(required) = tx.tons_to_emit().map_or(Some(self.stake_amount), ledger::emission_cost) else {
+            println!("⚠️ Dropped permit {}: Cost overflows", tx.task);
+            return;
+        };
+        if self.available_balance(&tx.sender) < required {
+            println!("⚠️ Dropped telemetry {}: Insufficient balance ({} required)", tx.task, required);
+            return;
+        }
+        self.record_nonce(&tx);
+        if tx.tons_to_emit().is_none() {
+            self.tasks_for_validation.push(tx.clone());
         }
+        self.tasks_for_mining.push(tx); // Burns need no sentinel validation; claims escrow their stake
+        self.save_chain();
+        self.refresh_wallet_cache();
     }
-    pub fn add_task_from_network(&mut self, tx: Transaction) { if !self.tasks_for_validation.iter().any(|t| t.task == tx.task) { self.tasks_for_validation.push(tx); } }
-    pub fn update_task_status_from_network(&mut self, task_id: &str, status: TaskStatus) { 
-        if let Some(pos) = self.tasks_for_validation.iter().position(|t| t.task == task_id) {
-            match status {
-                TaskStatus::Validated => { let t = self.tasks_for_validation.remove(pos); self.tasks_for_mining.push(t); }
-                TaskStatus::Rejected => { self.tasks_for_validation.remove(pos); }
-                _ => {}
+    pub fn add_review_from_network(&mut self, decision: ReviewDecision) {
+        if let Err(e) = self.review_task(decision) {
+            println!("⚠️ Ignored review decision: {}", e);
+        }
+    }
+    /// Apply a validator's signed verdict to the pending claim it decided.
+    pub fn update_task_status_from_network(&mut self, verdict: ValidationVerdict) {
+        if let Err(e) = verdict.verify(&self.validators) {
+            println!("⚠️ Ignored validation result: {}", e);
+            return;
+        }
+        let decided = verdict.claim;
+        let request_hash = decided.request_hash();
+        let Some(pos) = self.tasks_for_validation.iter().position(|t| t.request_hash() == request_hash) else { return };
+        let mut t = self.tasks_for_validation.remove(pos);
+        t.status = decided.status;
+        t.corroborated_by = decided.corroborated_by;
+        t.corroborates = decided.corroborates;
+        if t.corroborates.is_some() {
+            t.amount = 0; // Credited to the claim it confirms
+        }
+        if let Some(record) = verdict.rejection {
+            self.rejections.insert(t.task.clone(), record);
+        }
+        match t.status {
+            TaskStatus::UnderReview => self.review_queue.push(t),
+            _ => {
+                if t.status == TaskStatus::Validated {
+                    self.record_telemetry(&t);
+                }
+                self.tasks_for_mining.push(t);
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::verify_transaction_proof;
+    use crate::facility::GeoPoint;
+    use crate::transaction::CarbonCaptureClaim;
+    use bip39::Mnemonic;
+    use ed25519_dalek::SigningKey;
+    use std::ops::{Deref, DerefMut};
+    use std::sync::{Mutex, MutexGuard, Once};
+
+    const PRODUCER_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+    // The chain persists chain.json, wallets.json and keystore.json in the
+    // working directory: keep them out of the source tree, and let one test
+    // chain use them at a time.
+    fn scratch_dir() -> MutexGuard<'static, ()> {
+        static SCRATCH: Once = Once::new();
+        static FILES: Mutex<()> = Mutex::new(());
+        SCRATCH.call_once(|| {
+            let dir = std::env::temp_dir().join(format!("yuki-tests-{}", std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            std::env::set_current_dir(dir).unwrap();
+        });
+        FILES.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    struct TestChain {
+        blockchain: Blockchain,
+        _files: MutexGuard<'static, ()>,
+    }
+
+    impl Deref for TestChain {
+        type Target = Blockchain;
+        fn deref(&self) -> &Blockchain { &self.blockchain }
+    }
+
+    impl DerefMut for TestChain {
+        fn deref_mut(&mut self) -> &mut Blockchain { &mut self.blockchain }
+    }
+
+    fn address(key: &SigningKey) -> String {
+        crypto::address_from_public_key(&key.verifying_key())
+    }
+
+    // A single-validator chain at genesis.
+    fn test_chain(validator: &SigningKey, genesis_time: i64) -> TestChain {
+        let _files = scratch_dir();
+        let ledger = LedgerState::default();
+        let blockchain = Blockchain {
+            chain: vec![Block::new(0, genesis_time, vec![], "0".to_string(), ledger.root(), String::new())],
+            wallets: WalletManager::default(),
+            marketplace: Marketplace::default(),
+            methodologies: MethodologyRegistry::default(),
+            stake_amount: 500,
+            methane_gwp: Gwp::default(),
+            tasks_for_validation: Vec::new(),
+            tasks_for_mining: Vec::new(),
+            review_queue: Vec::new(),
+            authorized_sentinels: HashMap::new(),
+            facilities: HashMap::new(),
+            corroboration: CorroborationPolicy::default(),
+            used_signatures: HashSet::new(),
+            rejections: HashMap::new(),
+            sentinel_history: HashMap::new(),
+            sentinel_stats: HashMap::new(),
+            account_nonces: HashMap::new(),
+            genesis_allocations: BTreeMap::new(),
+            ledger,
+            validators: vec![hex::encode(validator.verifying_key().as_bytes())],
+            reviewers: Vec::new(),
+            block_tree: HashMap::new(),
+            mined_requests: HashSet::new(),
+            finalized_height: 0,
+            cast_votes: HashSet::new(),
+            precommit_locks: BTreeMap::new(),
+            vote_pool: Vec::new(),
+            producer: String::new(),
+        };
+        TestChain { blockchain, _files }
+    }
+
+    // A chain that mines with the producer key held in its unlocked
+    // keystore, with genesis balances for `allocations`.
+    fn producing_chain(allocations: &[(String, u64)]) -> (TestChain, SigningKey) {
+        let mnemonic = Mnemonic::parse_normalized(PRODUCER_MNEMONIC).unwrap();
+        let producer = crypto::signing_key_from_mnemonic(&mnemonic);
+        let mut blockchain = test_chain(&producer, Utc::now().timestamp() - 3_600);
+        blockchain.wallets.unlock("test").unwrap();
+        blockchain.producer = blockchain.wallets.restore_from_mnemonic(PRODUCER_MNEMONIC).unwrap().address;
+        blockchain.genesis_allocations = allocations.iter().cloned().collect();
+        blockchain.ledger = LedgerState::genesis(&blockchain.genesis_allocations);
+        let genesis = &blockchain.chain[0].header;
+        blockchain.chain[0] = Block::new(0, genesis.timestamp, vec![], "0".to_string(), blockchain.ledger.root(), String::new());
+        (blockchain, producer)
+    }
+
+    // Mine the pools in the slot after the tip.
+    fn mine(blockchain: &mut Blockchain) -> Block {
+        let timestamp = blockchain.chain.last().unwrap().header.timestamp + consensus::SLOT_DURATION_SECS;
+        blockchain.mine_block_at(timestamp).expect("block mined")
+    }
+
+    fn device() -> SigningKey {
+        SigningKey::from_bytes(&[9u8; 32])
+    }
+
+    // `sentinel-1` (signing with `device`) installed at a 50 tCO2/h capture
+    // plant run by `operator`.
+    fn install_sentinel(blockchain: &mut Blockchain, operator: &str) {
+        blockchain.facilities.insert("plant".to_string(), Facility {
+            id: "plant".to_string(),
+            operator: operator.to_string(),
+            geofence: Vec::new(),
+            capacity: BTreeMap::from([("carbon_capture".to_string(), 50.0)]),
+            sentinels: vec!["sentinel-1".to_string()],
+        });
+        blockchain.authorized_sentinels.insert("sentinel-1".to_string(), hex::encode(device().verifying_key().as_bytes()));
+    }
+
+    // A signed 10 tCO2 (1000 Yuki) capture reading from `sentinel-1` over
+    // the hour before `end`.
+    fn capture_claim(operator: &SigningKey, nonce: u64, end: i64) -> Transaction {
+        let sender = address(operator);
+        let mut kind = TransactionKind::CarbonCaptureClaim(CarbonCaptureClaim {
+            sentinel_id: "sentinel-1".to_string(),
+            tons_captured: 10.0,
+            measurement_start: Some(end - 3_600),
+            measurement_end: Some(end),
+            location: None,
+            packet_id: Some(format!("packet-{}", nonce)),
+            hardware_signature: String::new(),
+        });
+        sentinel::sign_telemetry(&device(), &mut kind);
+        let mut claim = Transaction::new(sender.clone(), Transaction::task_id(kind.name(), &sender, nonce), kind);
+        claim.sign(operator, nonce);
+        claim
+    }
+
+    // Re-sign `claim` (device and wallet) after editing its telemetry.
+    fn resign(claim: &mut Transaction, operator: &SigningKey, edit: impl FnOnce(&mut CarbonCaptureClaim)) {
+        if let TransactionKind::CarbonCaptureClaim(reading) = &mut claim.kind {
+            edit(reading);
+        }
+        sentinel::sign_telemetry(&device(), &mut claim.kind);
+        claim.sign(operator, claim.nonce);
+    }
+
+    fn review(reviewer: &SigningKey, request: &Transaction, approve: bool) -> ReviewDecision {
+        let (request_hash, timestamp) = (request.request_hash(), Utc::now().timestamp());
+        let reasoning = if approve { "Matches the plant log" } else { "Sensor drift" }.to_string();
+        let payload = ReviewDecision::payload(&request.task, &request_hash, approve, &reasoning, timestamp);
+        ReviewDecision {
+            task: request.task.clone(),
+            request_hash,
+            approve,
+            reasoning,
+            reviewer: hex::encode(reviewer.verifying_key().as_bytes()),
+            timestamp,
+            signature: crypto::sign(reviewer, &payload),
+        }
+    }
+
+    // A chain with a claim from `operator` held for review by `reviewer`.
+    fn reviewing_chain(operator: &SigningKey, reviewer: &SigningKey, measured_until: i64) -> (TestChain, Transaction) {
+        let (mut blockchain, _) = producing_chain(&[(address(operator), 1_000)]);
+        install_sentinel(&mut blockchain, &address(operator));
+        blockchain.reviewers.push(hex::encode(reviewer.verifying_key().as_bytes()));
+        let mut claim = capture_claim(operator, 1, measured_until);
+        claim.amount = 1_000;
+        claim.status = TaskStatus::UnderReview;
+        blockchain.review_queue.push(claim.clone());
+        (blockchain, claim)
+    }
+
+    fn mints(block: &Block) -> u64 {
+        block.transactions.iter().filter(|tx| tx.kind == TransactionKind::Mint).map(|tx| tx.amount).sum()
+    }
+
+    // An empty block sealed by `validator`, `slots` slots after its parent.
+    fn child(parent: &Block, slots: i64, validator: &SigningKey, state_root: &str) -> Block {
+        let timestamp = parent.header.timestamp + slots * consensus::SLOT_DURATION_SECS;
+        let producer = hex::encode(validator.verifying_key().as_bytes());
+        let mut block = Block::new(parent.header.index + 1, timestamp, vec![], parent.hash.clone(), state_root.to_string(), producer);
+        block.signature = crypto::sign(validator, &consensus::seal_payload(&block.hash));
+        block
+    }
+
+    // Genesis with a two-block canonical chain (a1, a2) and a three-block
+    // competing branch (b1..b3) forking at genesis, held in the block tree.
+    fn forked_chain(validator: &SigningKey) -> (TestChain, Vec<Block>, Vec<Block>) {
+        let mut blockchain = test_chain(validator, Utc::now().timestamp() - 600);
+        let root = blockchain.ledger.root();
+        let genesis = blockchain.chain[0].clone();
+
+        let a1 = child(&genesis, 1, validator, &root);
+        let a2 = child(&a1, 1, validator, &root);
+        let b1 = child(&genesis, 3, validator, &root);
+        let b2 = child(&b1, 1, validator, &root);
+        let b3 = child(&b2, 1, validator, &root);
+
+        blockchain.chain.extend([a1.clone(), a2.clone()]);
+        for block in [&b1, &b2, &b3] {
+            blockchain.block_tree.insert(block.hash.clone(), block.clone());
+        }
+        (blockchain, vec![a1, a2], vec![b1, b2, b3])
+    }
+
+    #[test]
+    fn longer_fork_replaces_the_canonical_chain() {
+        let validator = SigningKey::from_bytes(&[1u8; 32]);
+        let (mut blockchain, detached, branch) = forked_chain(&validator);
+
+        blockchain.apply_fork_choice();
+
+        let canonical: Vec<&str> = blockchain.chain[1..].iter().map(|b| b.hash.as_str()).collect();
+        assert_eq!(canonical, branch.iter().map(|b| b.hash.as_str()).collect::<Vec<_>>());
+        for block in &detached {
+            assert!(blockchain.block_tree.contains_key(&block.hash), "detached block kept as a side branch");
+        }
+        assert!(blockchain.validate_chain().is_ok());
+    }
+
+    #[test]
+    fn fork_detaching_a_precommitted_block_is_refused() {
+        let validator = SigningKey::from_bytes(&[1u8; 32]);
+        let (mut blockchain, detached, branch) = forked_chain(&validator);
+        blockchain.precommit_locks.insert(1, detached[0].hash.clone());
+
+        blockchain.apply_fork_choice();
+
+        assert_eq!(blockchain.chain.last().unwrap().hash, detached[1].hash);
+        assert!(!blockchain.block_tree.contains_key(&branch[2].hash));
+    }
+
+    #[test]
+    fn transaction_proof_round_trips() {
+        let validator = SigningKey::from_bytes(&[1u8; 32]);
+        let mut blockchain = test_chain(&validator, Utc::now().timestamp() - 600);
+        let permits: Vec<Transaction> = (1..=3).map(|nonce| Transaction::emission_permit("permit-wallet".to_string(), nonce, nonce)).collect();
+        let genesis = &blockchain.chain[0];
+        let block = Block::new(1, genesis.header.timestamp + consensus::SLOT_DURATION_SECS, permits.clone(), genesis.hash.clone(), blockchain.ledger.root(), String::new());
+        blockchain.chain.push(block);
+
+        let proof = blockchain.prove_transaction(&permits[1].task).unwrap();
+        assert_eq!(proof.leaf_index, 1);
+        assert_eq!(proof.transaction.canonical_bytes(), permits[1].canonical_bytes());
+        assert!(verify_transaction_proof(&proof).is_ok());
+
+        let mut forged = proof.clone();
+        forged.transaction.amount += 1;
+        assert!(verify_transaction_proof(&forged).is_err());
+
+        let mut rewritten = proof.clone();
+        rewritten.header.state_root = "0".repeat(64);
+        assert!(verify_transaction_proof(&rewritten).is_err());
+
+        assert!(blockchain.prove_transaction("unknown-task").is_none());
+    }
+
+    #[test]
+    fn gossiped_claim_enters_pending_whatever_status_it_carries() {
+        let operator = SigningKey::from_bytes(&[2u8; 32]);
+        let (mut blockchain, _) = producing_chain(&[(address(&operator), 1_000)]);
+        let mut claim = capture_claim(&operator, 1, Utc::now().timestamp() - 60);
+        claim.status = TaskStatus::Validated;
+        claim.corroborated_by = vec!["another-claim".to_string()];
+
+        blockchain.add_task_from_network(claim);
+        let block = mine(&mut blockchain);
+
+        assert_eq!(mints(&block), 0);
+        assert_eq!(blockchain.ledger.balance(&address(&operator)), 500); // Only the stake is locked
+        let queued = &blockchain.tasks_for_validation[0];
+        assert_eq!(queued.status, TaskStatus::PendingValidation);
+        assert_eq!(queued.amount, 1_000);
+        assert!(queued.corroborated_by.is_empty() && queued.corroborates.is_none());
+    }
+
+    #[test]
+    fn review_approves_packets_older_than_the_telemetry_history() {
+        let (operator, reviewer) = (SigningKey::from_bytes(&[2u8; 32]), SigningKey::from_bytes(&[3u8; 32]));
+        let (mut blockchain, claim) = reviewing_chain(&operator, &reviewer, Utc::now().timestamp() - 2 * TELEMETRY_HISTORY_SECS);
+
+        let approved = blockchain.review_task(review(&reviewer, &claim, true)).unwrap();
+
+        assert_eq!(approved.status, TaskStatus::Validated);
+        assert!(blockchain.review_queue.is_empty());
+        assert_eq!(mints(&mine(&mut blockchain)), 1_000);
+    }
+
+    #[test]
+    fn review_cannot_approve_a_packet_overlapping_accepted_telemetry() {
+        let (operator, reviewer) = (SigningKey::from_bytes(&[2u8; 32]), SigningKey::from_bytes(&[3u8; 32]));
+        let measured_until = Utc::now().timestamp() - 60;
+        let (mut blockchain, claim) = reviewing_chain(&operator, &reviewer, measured_until);
+        blockchain.record_telemetry(&capture_claim(&operator, 2, measured_until - 600)); // Accepted while the claim waited
+
+        let refused = blockchain.review_task(review(&reviewer, &claim, true)).unwrap_err();
+        assert!(refused.contains("can no longer be approved"), "{}", refused);
+        assert_eq!(blockchain.review_queue.len(), 1);
+
+        let rejected = blockchain.review_task(review(&reviewer, &claim, false)).unwrap();
+        assert_eq!(rejected.status, TaskStatus::Rejected);
+        assert_eq!(blockchain.rejections[&claim.task].code, RejectionCode::ReviewerRejected);
+        assert_eq!(blockchain.rejections[&claim.task].validator, hex::encode(reviewer.verifying_key().as_bytes()));
+    }
+
+    #[test]
+    fn task_ids_are_derived_from_the_sender_and_nonce() {
+        let (victim, squatter) = (SigningKey::from_bytes(&[2u8; 32]), SigningKey::from_bytes(&[4u8; 32]));
+        let (mut blockchain, _) = producing_chain(&[(address(&squatter), 1_000)]);
+        let victim_task = Transaction::task_id("carbon_capture", &address(&victim), 1);
+        let mut squat = Transaction::emission_permit(address(&squatter), 0, 1);
+        squat.task = victim_task;
+        squat.sign(&squatter, 1);
+
+        assert!(blockchain.request_emission_permit(squat.clone()).is_none());
+        blockchain.add_task_from_network(squat);
+        assert!(blockchain.tasks_for_mining.is_empty());
+
+        let mut permit = Transaction::emission_permit(address(&squatter), 0, 1);
+        permit.sign(&squatter, 1);
+        assert!(blockchain.request_emission_permit(permit).is_some());
+    }
+
+    #[test]
+    fn reorg_requeues_and_unwinds_a_settled_claim_on_the_detached_branch() {
+        let operator = SigningKey::from_bytes(&[2u8; 32]);
+        let (mut blockchain, producer) = producing_chain(&[(address(&operator), 1_000)]);
+        let genesis = blockchain.chain[0].clone();
+        let genesis_root = blockchain.ledger.root();
+        let mut claim = capture_claim(&operator, 1, Utc::now().timestamp() - 60);
+        claim.amount = 1_000;
+        claim.status = TaskStatus::Validated;
+        blockchain.tasks_for_mining.push(claim.clone());
+        let settled = mine(&mut blockchain);
+        assert_eq!(mints(&settled), 1_000);
+        assert_eq!(blockchain.ledger.balance(&address(&operator)), 2_000);
+
+        let b1 = child(&genesis, 2, &producer, &genesis_root);
+        let b2 = child(&b1, 1, &producer, &genesis_root);
+        for block in [&b1, &b2] {
+            blockchain.block_tree.insert(block.hash.clone(), block.clone());
+        }
+        blockchain.apply_fork_choice();
+
+        assert_eq!(blockchain.chain.last().unwrap().hash, b2.hash);
+        assert!(blockchain.block_tree.contains_key(&settled.hash));
+        assert_eq!(blockchain.ledger.balance(&address(&operator)), 1_000);
+        assert!(!blockchain.mined_requests.contains(&(claim.sender.clone(), claim.nonce)));
+        let requeued: Vec<&str> = blockchain.tasks_for_mining.iter().map(|t| t.task.as_str()).collect();
+        assert_eq!(requeued, [claim.task.as_str()]);
+        assert_eq!(blockchain.tasks_for_mining[0].status, TaskStatus::Validated);
+        assert_eq!(mints(&mine(&mut blockchain)), 1_000); // Settled again on the new branch
+    }
+
+    #[test]
+    fn geofenced_facility_requires_a_position_inside_it() {
+        let operator = SigningKey::from_bytes(&[2u8; 32]);
+        let (mut blockchain, _) = producing_chain(&[(address(&operator), 1_000)]);
+        install_sentinel(&mut blockchain, &address(&operator));
+        let corners = [(47.0, 8.0), (47.0, 8.1), (47.1, 8.1), (47.1, 8.0)];
+        blockchain.facilities.get_mut("plant").unwrap().geofence = corners.iter().map(|&(lat, lon)| GeoPoint { lat, lon }).collect();
+        let mut claim = capture_claim(&operator, 1, Utc::now().timestamp() - 60);
+
+        let (code, _) = blockchain.validate_industrial_packet(&claim).unwrap_err();
+        assert_eq!(code, RejectionCode::InvalidPacket);
+
+        resign(&mut claim, &operator, |reading| reading.location = Some(GeoPoint { lat: 47.5, lon: 8.05 }));
+        let (code, _) = blockchain.validate_industrial_packet(&claim).unwrap_err();
+        assert_eq!(code, RejectionCode::OutsideGeofence);
+
+        resign(&mut claim, &operator, |reading| reading.location = Some(GeoPoint { lat: 47.05, lon: 8.05 }));
+        assert_eq!(blockchain.validate_industrial_packet(&claim).unwrap().0, TaskStatus::Validated);
+    }
+
+    #[test]
+    fn tampered_chain_file_is_quarantined_on_load() {
+        let operator = SigningKey::from_bytes(&[2u8; 32]);
+        let (mut blockchain, _) = producing_chain(&[(address(&operator), 1_000)]);
+        let mut permit = Transaction::emission_permit(address(&operator), 3, 1);
+        permit.sign(&operator, 1);
+        blockchain.request_emission_permit(permit).unwrap();
+        mine(&mut blockchain);
+        blockchain.save_chain();
+        assert_eq!(Blockchain::new().ledger.balance(&address(&operator)), 700);
+
+        let mut saved: serde_json::Value = serde_json::from_str(&fs::read_to_string(CHAIN_FILE).unwrap()).unwrap();
+        assert_eq!(saved["chain"][1]["transactions"][1]["amount"], 300);
+        saved["chain"][1]["transactions"][1]["amount"] = 3.into(); // Burn less than the permit costs
+        let tampered = saved.to_string();
+        fs::write(CHAIN_FILE, &tampered).unwrap();
+
+        let reloaded = Blockchain::new();
+
+        assert_eq!(reloaded.chain.len(), 1, "started from genesis");
+        assert!(!std::path::Path::new(CHAIN_FILE).exists());
+        let quarantined = fs::read_dir(".").unwrap()
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| name.starts_with(&format!("{}.quarantined-", CHAIN_FILE)))
+            .map(|name| fs::read_to_string(name).unwrap())
+            .any(|data| data == tampered);
+        assert!(quarantined, "tampered ledger kept for forensics");
+    }
+
+    #[test]
+    fn stale_and_duplicate_nonces_are_refused() {
+        let operator = SigningKey::from_bytes(&[2u8; 32]);
+        let (mut blockchain, producer) = producing_chain(&[(address(&operator), 1_000)]);
+        let permit = |nonce| {
+            let mut permit = Transaction::emission_permit(address(&operator), 1, nonce);
+            permit.sign(&operator, nonce);
+            permit
+        };
+
+        assert!(blockchain.request_emission_permit(permit(2)).is_some());
+        assert!(blockchain.request_emission_permit(permit(2)).is_none(), "duplicate");
+        assert!(blockchain.request_emission_permit(permit(1)).is_none(), "stale");
+        blockchain.add_task_from_network(permit(1));
+        assert_eq!(blockchain.tasks_for_mining.len(), 1);
+        assert_eq!(blockchain.next_nonce(&address(&operator)), 3);
+
+        // A block mining the request again is not adopted, even with a consistent state root
+        let mined = mine(&mut blockchain);
+        let mut state = blockchain.ledger.clone();
+        state.apply_transactions(&mined.transactions).unwrap();
+        let timestamp = mined.header.timestamp + consensus::SLOT_DURATION_SECS;
+        let mut replay = Block::new(2, timestamp, mined.transactions.clone(), mined.hash.clone(), state.root(), mined.header.producer.clone());
+        replay.signature = crypto::sign(&producer, &consensus::seal_payload(&replay.hash));
+        blockchain.add_block_from_network(replay);
+        assert_eq!(blockchain.chain.last().unwrap().hash, mined.hash);
+        assert_eq!(blockchain.ledger.balance(&address(&operator)), 900);
+    }
+
+    #[test]
+    fn forged_or_unregistered_sentinel_packets_are_rejected() {
+        let operator = SigningKey::from_bytes(&[2u8; 32]);
+        let (mut blockchain, _) = producing_chain(&[(address(&operator), 1_000)]);
+        install_sentinel(&mut blockchain, &address(&operator));
+        let genuine = capture_claim(&operator, 1, Utc::now().timestamp() - 60);
+        let rejection = |blockchain: &Blockchain, claim: &Transaction| blockchain.validate_industrial_packet(claim).unwrap_err().0;
+        assert!(blockchain.validate_industrial_packet(&genuine).is_ok());
+
+        let mut unregistered = genuine.clone();
+        resign(&mut unregistered, &operator, |reading| reading.sentinel_id = "sentinel-2".to_string());
+        assert_eq!(rejection(&blockchain, &unregistered), RejectionCode::UnauthorizedHardware);
+
+        let mut foreign_device = genuine.clone();
+        sentinel::sign_telemetry(&SigningKey::from_bytes(&[8u8; 32]), &mut foreign_device.kind);
+        foreign_device.sign(&operator, 1);
+        assert_eq!(rejection(&blockchain, &foreign_device), RejectionCode::AttestationFailed);
+
+        let mut inflated = genuine.clone();
+        if let TransactionKind::CarbonCaptureClaim(reading) = &mut inflated.kind {
+            reading.tons_captured = 40.0; // Edited after the device signed it
+        }
+        inflated.sign(&operator, 1);
+        assert_eq!(rejection(&blockchain, &inflated), RejectionCode::AttestationFailed);
+
+        let mut unsigned = genuine.clone();
+        unsigned.kind.set_hardware_signature(String::new());
+        unsigned.sign(&operator, 1);
+        assert_eq!(rejection(&blockchain, &unsigned), RejectionCode::AttestationFailed);
+    }
+
+    #[test]
+    fn review_decisions_only_apply_from_reviewers_to_the_signed_request() {
+        let (operator, reviewer) = (SigningKey::from_bytes(&[2u8; 32]), SigningKey::from_bytes(&[3u8; 32]));
+        let (mut blockchain, claim) = reviewing_chain(&operator, &reviewer, Utc::now().timestamp() - 60);
+
+        let outsider = blockchain.review_task(review(&SigningKey::from_bytes(&[7u8; 32]), &claim, true)).unwrap_err();
+        assert_eq!(outsider, "Signer is not an authorized reviewer");
+
+        let mut other_request = claim.clone(); // Same task id, other telemetry
+        resign(&mut other_request, &operator, |reading| reading.tons_captured = 12.0);
+        assert!(blockchain.review_task(review(&reviewer, &other_request, true)).unwrap_err().contains("not awaiting review"));
+
+        let mut reworded = review(&reviewer, &claim, false);
+        reworded.approve = true;
+        assert!(blockchain.review_task(reworded).is_err());
+        assert_eq!(blockchain.review_queue.len(), 1);
+    }
+
+    #[test]
+    fn gossiped_rejections_apply_only_when_signed_by_a_validator() {
+        let operator = SigningKey::from_bytes(&[2u8; 32]);
+        let mut claim = capture_claim(&operator, 1, Utc::now().timestamp() - 60);
+        sentinel::sign_telemetry(&SigningKey::from_bytes(&[8u8; 32]), &mut claim.kind);
+        claim.sign(&operator, 1);
+        claim.amount = 1_000;
+
+        let verdict = {
+            let (mut validating, _) = producing_chain(&[(address(&operator), 1_000)]);
+            install_sentinel(&mut validating, &address(&operator));
+            validating.tasks_for_validation.push(claim.clone());
+            validating.run_automated_validation().pop().expect("verdict signed")
+        };
+        assert_eq!(verdict.claim.status, TaskStatus::Rejected);
+        assert_eq!(verdict.rejection.as_ref().unwrap().code, RejectionCode::AttestationFailed);
+
+        let (mut blockchain, _) = producing_chain(&[(address(&operator), 1_000)]);
+        blockchain.tasks_for_validation.push(claim.clone());
+
+        let mut tampered = verdict.clone();
+        tampered.rejection.as_mut().unwrap().code = RejectionCode::ReplayAttack;
+        blockchain.update_task_status_from_network(tampered);
+        let outsider = SigningKey::from_bytes(&[7u8; 32]);
+        let mut forged = verdict.clone();
+        forged.validator = hex::encode(outsider.verifying_key().as_bytes());
+        forged.rejection.as_mut().unwrap().validator = forged.validator.clone();
+        forged.signature = crypto::sign(&outsider, &ValidationVerdict::payload(&forged.claim, forged.rejection.as_ref()));
+        blockchain.update_task_status_from_network(forged);
+        assert_eq!(blockchain.tasks_for_validation.len(), 1);
+        assert!(blockchain.rejections.is_empty());
+
+        blockchain.update_task_status_from_network(verdict);
+        assert!(blockchain.tasks_for_validation.is_empty());
+        assert_eq!(blockchain.rejections[&claim.task].code, RejectionCode::AttestationFailed);
+        let decided = &blockchain.tasks_for_mining[0];
+        assert_eq!((&decided.task, &decided.status), (&claim.task, &TaskStatus::Rejected));
+    }
+
+    // A 20 tCO2 claim from `sentinel-1` and a matching reading from a second
+    // sentinel at the same plant, both pending and above the default
+    // corroboration threshold.
+    fn corroborating_chain(operator: &SigningKey, end: i64) -> (TestChain, Transaction, Transaction) {
+        let (mut blockchain, _) = producing_chain(&[(address(operator), 1_000)]);
+        install_sentinel(&mut blockchain, &address(operator));
+        let second = SigningKey::from_bytes(&[10u8; 32]);
+        blockchain.authorized_sentinels.insert("sentinel-2".to_string(), hex::encode(second.verifying_key().as_bytes()));
+        blockchain.facilities.get_mut("plant").unwrap().sentinels.push("sentinel-2".to_string());
+
+        let mut claim = capture_claim(operator, 1, end);
+        resign(&mut claim, operator, |reading| reading.tons_captured = 20.0);
+        let mut witness = capture_claim(operator, 2, end);
+        if let TransactionKind::CarbonCaptureClaim(reading) = &mut witness.kind {
+            reading.sentinel_id = "sentinel-2".to_string();
+            reading.tons_captured = 20.5;
+        }
+        sentinel::sign_telemetry(&second, &mut witness.kind);
+        witness.sign(operator, 2);
+        for tx in [&mut claim, &mut witness] {
+            tx.amount = blockchain.calculate_industrial_credit(&tx.kind);
+            blockchain.tasks_for_validation.push(tx.clone());
+        }
+        (blockchain, claim, witness)
+    }
+
+    #[test]
+    fn high_value_claims_consume_a_matching_sentinel_reading() {
+        let operator = SigningKey::from_bytes(&[2u8; 32]);
+        let (mut blockchain, claim, witness) = corroborating_chain(&operator, Utc::now().timestamp() - 60);
+        assert!(blockchain.corroboration.applies_to(claim.amount));
+
+        assert_eq!(blockchain.run_automated_validation().len(), 2);
+
+        assert!(blockchain.tasks_for_validation.is_empty());
+        let decided = |task: &str| blockchain.tasks_for_mining.iter().find(|t| t.task == task).unwrap();
+        let (claim, witness) = (decided(&claim.task), decided(&witness.task));
+        assert_eq!((&claim.status, claim.amount), (&TaskStatus::Validated, 2_000));
+        assert_eq!(claim.corroborated_by, vec!["packet-2".to_string()]);
+        assert_eq!((&witness.status, witness.amount), (&TaskStatus::Validated, 0));
+        assert_eq!(witness.corroborates.as_ref(), Some(&claim.task));
+    }
+
+    #[test]
+    fn uncorroborated_claims_wait_then_are_rejected_without_consuming_packets() {
+        let operator = SigningKey::from_bytes(&[2u8; 32]);
+        {
+            let (mut blockchain, claim, _) = corroborating_chain(&operator, Utc::now().timestamp() - 60);
+            blockchain.tasks_for_validation.truncate(1);
+            assert!(blockchain.run_automated_validation().is_empty());
+            assert_eq!(blockchain.tasks_for_validation[0].task, claim.task);
+            assert!(blockchain.tasks_for_mining.is_empty() && blockchain.used_signatures.is_empty());
+        }
+
+        let (mut blockchain, claim, witness) = corroborating_chain(&operator, Utc::now().timestamp() - 3_700);
+        blockchain.corroboration.required = 3; // One matching reading is no longer enough
+        blockchain.run_automated_validation();
+
+        assert!(blockchain.tasks_for_validation.is_empty());
+        for task in [&claim.task, &witness.task] {
+            let decided = blockchain.tasks_for_mining.iter().find(|t| &t.task == task).unwrap();
+            assert_eq!(decided.status, TaskStatus::Rejected);
+            assert!(decided.corroborated_by.is_empty() && decided.corroborates.is_none());
+            assert_eq!(blockchain.rejections[task].code, RejectionCode::Uncorroborated);
+        }
+        assert!(blockchain.used_signatures.is_empty());
+    }
+
+    #[test]
+    fn claims_from_a_wallet_not_operating_the_facility_are_refused() {
+        let (operator, intruder) = (SigningKey::from_bytes(&[2u8; 32]), SigningKey::from_bytes(&[4u8; 32]));
+        let (mut blockchain, _) = producing_chain(&[(address(&operator), 1_000), (address(&intruder), 1_000)]);
+        install_sentinel(&mut blockchain, &address(&operator));
+        let claim = capture_claim(&intruder, 1, Utc::now().timestamp() - 60); // Genuine telemetry, wrong wallet
+
+        assert_eq!(blockchain.validate_industrial_packet(&claim).unwrap_err().0, RejectionCode::OperatorMismatch);
+        assert!(blockchain.submit_industrial_task(claim).is_none());
+        assert!(blockchain.tasks_for_validation.is_empty() && blockchain.tasks_for_mining.is_empty());
+    }
+}
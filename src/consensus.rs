@@ -0,0 +1,40 @@
+use crate::crypto::CanonicalEncoder;
+use crate::utils;
+
+const VALIDATORS_FILE: &str = "validators.json"; // JSON array of hex ed25519 public keys
+
+/// Proof-of-Authority slot length. Time is divided into slots and each slot
+/// belongs to one validator (round-robin), so at most one block per slot.
+pub const SLOT_DURATION_SECS: i64 = 10;
+
+pub fn slot_at(timestamp: i64) -> i64 {
+    timestamp.div_euclid(SLOT_DURATION_SECS)
+}
+
+/// The validator allowed to produce a block in `slot`.
+pub fn slot_leader(validators: &[String], slot: i64) -> Option<&String> {
+    if validators.is_empty() {
+        return None;
+    }
+    validators.get(slot.rem_euclid(validators.len() as i64) as usize)
+}
+
+/// Seconds until `validator` next owns a slot after `timestamp`.
+pub fn seconds_until_turn(validators: &[String], validator: &str, timestamp: i64) -> Option<i64> {
+    let current = slot_at(timestamp);
+    (1..=validators.len() as i64)
+        .map(|offset| current + offset)
+        .find(|slot| slot_leader(validators, *slot).is_some_and(|v| v == validator))
+        .map(|slot| slot * SLOT_DURATION_SECS - timestamp)
+}
+
+/// Bytes the producer signs to seal a block.
+pub fn seal_payload(block_hash: &str) -> Vec<u8> {
+    CanonicalEncoder::new("yuki-block-seal-v1").str(block_hash).finish()
+}
+
+/// Authorized producer set configured for genesis.
+pub fn load_validator_set() -> Vec<String> {
+    let validators: Vec<String> = utils::load_config(VALIDATORS_FILE);
+    validators.into_iter().map(|v| v.trim().to_lowercase()).collect()
+}
@@ -0,0 +1,41 @@
+use serde::{Serialize, Deserialize};
+
+/// Independent confirmation for high-value claims: before a claim worth at
+/// least `credit_threshold` Yuki is approved, `required` sentinels (the
+/// claimant's included) at the same facility must report a matching
+/// measurement. Corroborating packets are consumed without minting, so the
+/// same captured ton is only ever credited once.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CorroborationPolicy {
+    pub credit_threshold: u64,
+    pub required: usize,            // M of the facility's N sentinels
+    pub value_tolerance: f64,       // Relative difference allowed between readings
+    pub window_tolerance_secs: i64, // Allowed offset of the measurement period bounds
+    pub timeout_secs: i64,          // Claims still uncorroborated this long after their period are rejected
+}
+
+impl Default for CorroborationPolicy {
+    fn default() -> Self {
+        Self {
+            credit_threshold: 2_000, // 20 tCO2 of direct air capture
+            required: 2,
+            value_tolerance: 0.1,
+            window_tolerance_secs: 300,
+            timeout_secs: 3_600,
+        }
+    }
+}
+
+impl CorroborationPolicy {
+    pub fn applies_to(&self, credits: u64) -> bool {
+        self.required > 1 && credits >= self.credit_threshold
+    }
+
+    /// Whether two readings `(quantity, (start, end))` describe the same
+    /// measurement within the tolerances.
+    pub fn matches(&self, (a, (a_start, a_end)): (f64, (i64, i64)), (b, (b_start, b_end)): (f64, (i64, i64))) -> bool {
+        (a - b).abs() <= self.value_tolerance * a.abs().max(b.abs())
+            && (a_start - b_start).abs() <= self.window_tolerance_secs
+            && (a_end - b_end).abs() <= self.window_tolerance_secs
+    }
+}
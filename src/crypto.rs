@@ -0,0 +1,134 @@
+use crate::utils::hash_bytes;
+use bip39::Mnemonic;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// SLIP-0010 ed25519 derivation path for Yuki wallets: m/44'/7777'/0'/0'/0'
+/// (ed25519 only supports hardened children, so every level is hardened).
+pub const DERIVATION_PATH: [u32; 5] = [44, 7777, 0, 0, 0];
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// Derive the wallet signing key from a BIP39 mnemonic (empty passphrase).
+pub fn signing_key_from_mnemonic(mnemonic: &Mnemonic) -> SigningKey {
+    let seed = mnemonic.to_seed("");
+    derive_signing_key(&seed, &DERIVATION_PATH)
+}
+
+/// SLIP-0010 master key + hardened child derivation for the ed25519 curve.
+pub fn derive_signing_key(seed: &[u8], path: &[u32]) -> SigningKey {
+    let (mut key, mut chain_code) = hmac_split(b"ed25519 seed", &[seed]);
+
+    for index in path {
+        let hardened = (index | HARDENED_OFFSET).to_be_bytes();
+        (key, chain_code) = hmac_split(&chain_code, &[&[0u8], &key, &hardened]);
+    }
+
+    SigningKey::from_bytes(&key)
+}
+
+/// Wallet addresses are the SHA256 of the raw ed25519 public key.
+pub fn address_from_public_key(public_key: &VerifyingKey) -> String {
+    hash_bytes(public_key.as_bytes())
+}
+
+fn hmac_split(key: &[u8], parts: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    let output = mac.finalize().into_bytes();
+
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+/// Unambiguous byte encoding for signed data: a domain tag followed by
+/// length-prefixed strings and big-endian integers, so no two field
+/// combinations can produce the same bytes.
+pub struct CanonicalEncoder {
+    bytes: Vec<u8>,
+}
+
+impl CanonicalEncoder {
+    pub fn new(domain: &str) -> Self {
+        let encoder = Self { bytes: Vec::new() };
+        encoder.str(domain)
+    }
+
+    pub fn str(self, value: &str) -> Self {
+        self.bytes(value.as_bytes())
+    }
+
+    pub fn bytes(mut self, value: &[u8]) -> Self {
+        self.bytes.extend_from_slice(&(value.len() as u32).to_be_bytes());
+        self.bytes.extend_from_slice(value);
+        self
+    }
+
+    pub fn u32(mut self, value: u32) -> Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn u64(mut self, value: u64) -> Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn i64(mut self, value: i64) -> Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+pub fn sign(key: &SigningKey, message: &[u8]) -> String {
+    hex::encode(key.sign(message).to_bytes())
+}
+
+/// Check a hex signature against a hex public key; returns the parsed key on success.
+pub fn verify(public_key: &str, message: &[u8], signature: &str) -> Result<VerifyingKey, String> {
+    let key_bytes: [u8; 32] = hex::decode(public_key).ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or("Malformed public key")?;
+    let public_key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| "Invalid public key")?;
+
+    let sig_bytes: [u8; 64] = hex::decode(signature).ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or("Malformed signature")?;
+
+    public_key.verify(message, &Signature::from_bytes(&sig_bytes))
+        .map_err(|_| "Signature verification failed".to_string())?;
+    Ok(public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SLIP-0010 test vector 1 for ed25519, chain m/0H/1H/2H/2H/1000000000H
+    #[test]
+    fn slip10_test_vector_1() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let private_key = |path: &[u32]| hex::encode(derive_signing_key(&seed, path).to_bytes());
+
+        assert_eq!(private_key(&[]), "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7");
+        assert_eq!(private_key(&[0]), "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3");
+        assert_eq!(private_key(&[0, 1]), "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2");
+        assert_eq!(private_key(&[0, 1, 2]), "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9");
+        assert_eq!(private_key(&[0, 1, 2, 2]), "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662");
+        assert_eq!(private_key(&[0, 1, 2, 2, 1_000_000_000]), "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793");
+
+        let public_key = derive_signing_key(&seed, &[0, 1, 2, 2, 1_000_000_000]).verifying_key();
+        assert_eq!(hex::encode(public_key.as_bytes()), "3c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a");
+    }
+}
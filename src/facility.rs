@@ -0,0 +1,90 @@
+use crate::methodology::PhysicalLimits;
+use crate::utils;
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+
+const FACILITIES_FILE: &str = "facilities.json"; // JSON array of facilities registered at genesis
+
+/// WGS84 coordinate in decimal degrees.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+/// A registered industrial site. Its sentinels' claims are credited to the
+/// operator wallet only, and its equipment capacity bounds what they report.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Facility {
+    pub id: String,
+    pub operator: String, // Wallet address that receives the facility's credits
+    #[serde(default)]
+    pub geofence: Vec<GeoPoint>,           // Site boundary polygon (empty: not surveyed)
+    pub capacity: BTreeMap<String, f64>,   // Claim type -> equipment capacity per hour, in the methodology's unit
+    #[serde(default)]
+    pub sentinels: Vec<String>,
+}
+
+impl Facility {
+    /// Whether `point` lies inside the geofence (even-odd ray casting on
+    /// lat/lon; fine for site-sized polygons away from the antimeridian).
+    /// A facility without a surveyed geofence contains every point.
+    pub fn contains(&self, point: GeoPoint) -> bool {
+        if self.geofence.is_empty() {
+            return true;
+        }
+        let mut inside = false;
+        let mut previous = self.geofence[self.geofence.len() - 1];
+        for &vertex in &self.geofence {
+            if (vertex.lat > point.lat) != (previous.lat > point.lat) {
+                let crossing = vertex.lon + (point.lat - vertex.lat) * (previous.lon - vertex.lon) / (previous.lat - vertex.lat);
+                if point.lon < crossing {
+                    inside = !inside;
+                }
+            }
+            previous = vertex;
+        }
+        inside
+    }
+
+    /// The methodology's limits scaled to this facility's equipment: one
+    /// hour of capacity per packet and pro rata over the rolling window.
+    /// None if the facility has no equipment registered for the activity.
+    pub fn limits(&self, task_type: &str, reference: PhysicalLimits) -> Option<PhysicalLimits> {
+        let per_hour = *self.capacity.get(task_type)?;
+        Some(PhysicalLimits {
+            max_per_packet: per_hour,
+            max_per_window: per_hour * reference.window_secs as f64 / 3600.0,
+            ..reference
+        })
+    }
+}
+
+/// Facilities configured for genesis.
+pub fn load_facilities() -> Vec<Facility> {
+    utils::load_config(FACILITIES_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site(geofence: Vec<GeoPoint>) -> Facility {
+        Facility { id: "plant".to_string(), operator: "operator".to_string(), geofence, capacity: BTreeMap::new(), sentinels: Vec::new() }
+    }
+
+    #[test]
+    fn geofence_contains_points_inside_the_polygon() {
+        let corners = [(47.0, 8.0), (47.0, 8.1), (47.1, 8.1), (47.1, 8.0)];
+        let facility = site(corners.iter().map(|&(lat, lon)| GeoPoint { lat, lon }).collect());
+
+        assert!(facility.contains(GeoPoint { lat: 47.05, lon: 8.05 }));
+        assert!(!facility.contains(GeoPoint { lat: 47.05, lon: 8.2 }));
+        assert!(!facility.contains(GeoPoint { lat: 46.9, lon: 8.05 }));
+    }
+
+    #[test]
+    fn unsurveyed_facility_contains_every_point() {
+        assert!(site(Vec::new()).contains(GeoPoint { lat: -33.9, lon: 151.2 }));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::crypto::{self, CanonicalEncoder};
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+
+/// Two-phase BFT voting (Tendermint style). A validator prevotes for the
+/// block it holds at a height; once it sees a prevote quorum for that block it
+/// precommits. A precommit quorum is a finality certificate: the block and all
+/// its ancestors can never be reorganized away.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VoteType {
+    Prevote,
+    Precommit,
+}
+
+impl VoteType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            VoteType::Prevote => "prevote",
+            VoteType::Precommit => "precommit",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Vote {
+    pub vote_type: VoteType,
+    pub height: u64,
+    pub block_hash: String,
+    pub validator: String, // Hex ed25519 public key (must be in the validator set)
+    pub signature: String,
+}
+
+impl Vote {
+    pub fn payload(vote_type: VoteType, height: u64, block_hash: &str) -> Vec<u8> {
+        CanonicalEncoder::new("yuki-vote-v1")
+            .str(vote_type.as_str())
+            .u64(height)
+            .str(block_hash)
+            .finish()
+    }
+
+    pub fn verify(&self, validators: &[String]) -> Result<(), String> {
+        if !validators.contains(&self.validator) {
+            return Err("Voter is not an authorized validator".to_string());
+        }
+        crypto::verify(&self.validator, &Self::payload(self.vote_type, self.height, &self.block_hash), &self.signature)
+            .map(|_| ())
+    }
+}
+
+/// Aggregated precommits from more than two thirds of the validator set.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FinalityCertificate {
+    pub height: u64,
+    pub block_hash: String,
+    pub precommits: Vec<Vote>,
+}
+
+impl FinalityCertificate {
+    pub fn verify(&self, validators: &[String]) -> Result<(), String> {
+        let mut signers = HashSet::new();
+        for vote in &self.precommits {
+            if vote.vote_type != VoteType::Precommit || vote.height != self.height || vote.block_hash != self.block_hash {
+                return Err("Certificate contains a vote for another block".to_string());
+            }
+            vote.verify(validators)?;
+            signers.insert(vote.validator.as_str());
+        }
+        if signers.len() < quorum(validators.len()) {
+            return Err(format!("Only {} of {} required precommits", signers.len(), quorum(validators.len())));
+        }
+        Ok(())
+    }
+}
+
+/// Votes needed for a BFT quorum: strictly more than 2/3 of `validators`.
+pub fn quorum(validators: usize) -> usize {
+    validators * 2 / 3 + 1
+}
@@ -0,0 +1,205 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use ed25519_dalek::SigningKey;
+use rand::{RngCore, thread_rng};
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::{env, fs};
+
+const KEYSTORE_FILE: &str = "keystore.json";
+
+// scrypt cost: N = 2^15, r = 8, p = 1 (~32 MiB per derivation)
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const KEY_LEN: usize = 32;
+
+/// Ethereum-keystore-like record: one encrypted ed25519 secret per wallet.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KeystoreEntry {
+    pub version: u32,
+    pub address: String,
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KeystoreCrypto {
+    pub cipher: String, // "aes-256-gcm" (address bound as associated data)
+    pub ciphertext: String,
+    pub cipherparams: CipherParams,
+    pub kdf: String, // "scrypt"
+    pub kdfparams: KdfParams,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CipherParams {
+    pub nonce: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    pub dklen: usize,
+    pub salt: String,
+}
+
+struct Unlocked {
+    password: String,
+    keys: HashMap<String, SigningKey>,
+}
+
+/// Password-protected key storage. Secrets only exist in memory while unlocked.
+pub struct Keystore {
+    path: String,
+    entries: HashMap<String, KeystoreEntry>,
+    unlocked: Option<Unlocked>,
+}
+
+impl Default for Keystore {
+    fn default() -> Self {
+        Self { path: KEYSTORE_FILE.to_string(), entries: HashMap::new(), unlocked: None }
+    }
+}
+
+impl Keystore {
+    /// Load the keystore from `YUKI_KEYSTORE` (default: keystore.json), locked.
+    /// Only a missing file means an empty keystore: an unreadable or corrupt
+    /// one stops the node, since starting empty would let the next save
+    /// overwrite every encrypted key.
+    pub fn load() -> Self {
+        let path = env::var("YUKI_KEYSTORE").unwrap_or_else(|_| KEYSTORE_FILE.to_string());
+        let entries = match fs::read_to_string(&path) {
+            Ok(data) => serde_json::from_str(&data)
+                .unwrap_or_else(|e| panic!("Refusing to start: keystore '{}' is corrupt ({}). Restore it from a backup.", path, e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(e) => panic!("Refusing to start: cannot read keystore '{}' ({})", path, e),
+        };
+        Self { path, entries, unlocked: None }
+    }
+
+    /// Write to a temporary file and rename it over the keystore, so a crash
+    /// mid-write never leaves a torn file behind.
+    fn save(&self) -> Result<(), String> {
+        let data = serde_json::to_string_pretty(&self.entries).map_err(|e| format!("Cannot serialize keystore ({})", e))?;
+        let temp = format!("{}.tmp", self.path);
+        fs::write(&temp, data)
+            .and_then(|_| fs::rename(&temp, &self.path))
+            .map_err(|e| format!("Cannot save keystore '{}' ({})", self.path, e))
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked.is_some()
+    }
+
+    /// Decrypt every entry with the password. An empty keystore adopts the password.
+    pub fn unlock(&mut self, password: &str) -> Result<usize, String> {
+        let mut keys = HashMap::new();
+        for entry in self.entries.values() {
+            keys.insert(entry.address.clone(), decrypt_entry(entry, password)?);
+        }
+        self.unlocked = Some(Unlocked { password: password.to_string(), keys });
+        Ok(self.entries.len())
+    }
+
+    pub fn lock(&mut self) {
+        self.unlocked = None;
+    }
+
+    /// Encrypt and persist a key. Requires the keystore to be unlocked.
+    pub fn insert(&mut self, address: String, key: SigningKey) -> Result<(), String> {
+        let unlocked = self.unlocked.as_mut().ok_or("Keystore is locked. Unlock it first.")?;
+        let entry = encrypt_entry(&address, &key, &unlocked.password)?;
+        self.entries.insert(address.clone(), entry);
+        unlocked.keys.insert(address, key);
+        self.save()
+    }
+
+    pub fn get(&self, address: &str) -> Option<&SigningKey> {
+        self.unlocked.as_ref()?.keys.get(address)
+    }
+}
+
+fn derive_key(password: &str, params: &KdfParams) -> Result<[u8; KEY_LEN], String> {
+    let salt = hex::decode(&params.salt).map_err(|_| "Malformed keystore salt")?;
+    let scrypt_params = scrypt::Params::new(params.log_n, params.r, params.p, params.dklen)
+        .map_err(|_| "Invalid keystore KDF parameters")?;
+    let mut key = [0u8; KEY_LEN];
+    scrypt::scrypt(password.as_bytes(), &salt, &scrypt_params, &mut key)
+        .map_err(|_| "Keystore KDF failed")?;
+    Ok(key)
+}
+
+fn encrypt_entry(address: &str, key: &SigningKey, password: &str) -> Result<KeystoreEntry, String> {
+    let mut salt = [0u8; 32];
+    let mut nonce = [0u8; 12];
+    thread_rng().fill_bytes(&mut salt);
+    thread_rng().fill_bytes(&mut nonce);
+
+    let kdfparams = KdfParams {
+        log_n: SCRYPT_LOG_N,
+        r: SCRYPT_R,
+        p: SCRYPT_P,
+        dklen: KEY_LEN,
+        salt: hex::encode(salt),
+    };
+    let cipher = Aes256Gcm::new_from_slice(&derive_key(password, &kdfparams)?)
+        .map_err(|_| "Invalid cipher key")?;
+    let secret = key.to_bytes();
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: &secret, aad: address.as_bytes() })
+        .map_err(|_| "Keystore encryption failed")?;
+
+    Ok(KeystoreEntry {
+        version: 1,
+        address: address.to_string(),
+        crypto: KeystoreCrypto {
+            cipher: "aes-256-gcm".to_string(),
+            ciphertext: hex::encode(ciphertext),
+            cipherparams: CipherParams { nonce: hex::encode(nonce) },
+            kdf: "scrypt".to_string(),
+            kdfparams,
+        },
+    })
+}
+
+fn decrypt_entry(entry: &KeystoreEntry, password: &str) -> Result<SigningKey, String> {
+    if entry.crypto.cipher != "aes-256-gcm" || entry.crypto.kdf != "scrypt" {
+        return Err(format!("Unsupported keystore format for {}", entry.address));
+    }
+
+    let nonce = hex::decode(&entry.crypto.cipherparams.nonce).map_err(|_| "Malformed keystore nonce")?;
+    if nonce.len() != 12 {
+        return Err("Malformed keystore nonce".to_string());
+    }
+    let ciphertext = hex::decode(&entry.crypto.ciphertext).map_err(|_| "Malformed keystore ciphertext")?;
+
+    let cipher = Aes256Gcm::new_from_slice(&derive_key(password, &entry.crypto.kdfparams)?)
+        .map_err(|_| "Invalid cipher key")?;
+    let secret = cipher
+        .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: entry.address.as_bytes() })
+        .map_err(|_| "Wrong keystore password (or corrupted keystore)")?;
+
+    let bytes: [u8; 32] = secret.try_into().map_err(|_| "Malformed keystore secret")?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_round_trips_with_the_password_only() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let entry = encrypt_entry("wallet", &key, "correct horse").unwrap();
+
+        assert_eq!(decrypt_entry(&entry, "correct horse").unwrap().to_bytes(), key.to_bytes());
+        assert!(decrypt_entry(&entry, "wrong password").is_err());
+
+        let mut moved = entry.clone();
+        moved.address = "another wallet".to_string(); // The address is bound as associated data
+        assert!(decrypt_entry(&moved, "correct horse").is_err());
+    }
+}
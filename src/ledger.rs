@@ -0,0 +1,248 @@
+use crate::crypto::CanonicalEncoder;
+use crate::methodology::MethodologyRegistry;
+use crate::transaction::{Transaction, TaskStatus, TransactionKind};
+use crate::utils::{self, hash_bytes};
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+const ALLOCATIONS_FILE: &str = "allocations.json"; // JSON map of address -> genesis Yuki balance
+
+pub const COST_PER_TON: u64 = 100; // 1 Ton Emission costs 100 Yuki (Ratio 1:1 with Capture)
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Account {
+    pub balance: u64, // Yuki
+    pub locked: u64,  // Yuki escrowed as claim stake
+    pub nonce: u64,   // Last mined nonce
+}
+
+/// Account state derived purely from block contents. Every node replays the
+/// same blocks from the same genesis allocations, so balances agree
+/// everywhere; `wallets.json` only caches the result.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct LedgerState {
+    pub accounts: BTreeMap<String, Account>,
+    #[serde(default)]
+    pub escrows: BTreeSet<(String, u64)>, // (sender, nonce) of claims whose stake is locked pending a decision
+}
+
+impl LedgerState {
+    pub fn genesis(allocations: &BTreeMap<String, u64>) -> Self {
+        let accounts = allocations.iter()
+            .map(|(address, balance)| (address.clone(), Account { balance: *balance, locked: 0, nonce: 0 }))
+            .collect();
+        Self { accounts, escrows: BTreeSet::new() }
+    }
+
+    pub fn balance(&self, address: &str) -> u64 {
+        self.accounts.get(address).map_or(0, |a| a.balance)
+    }
+
+    pub fn is_escrowed(&self, address: &str, nonce: u64) -> bool {
+        self.escrows.contains(&(address.to_string(), nonce))
+    }
+
+    /// State transition for one mined transaction. Requests only advance
+    /// the sender's nonce; balances change solely through movements.
+    pub fn apply_transaction(&mut self, tx: &Transaction) -> Result<(), String> {
+        let account = self.accounts.entry(tx.sender.clone()).or_default();
+        let insufficient = |what: &str, have: u64| format!("{} {} of {} exceeds {} for {}", tx.kind.name(), tx.amount, tx.sender, what, have);
+        let escrow = (tx.sender.clone(), tx.nonce);
+        match tx.kind {
+            TransactionKind::StakeLock => {
+                if account.balance < tx.amount { return Err(insufficient("balance", account.balance)); }
+                if !self.escrows.insert(escrow) { return Err(format!("Stake for {} is already locked", tx.task)); }
+                account.balance -= tx.amount;
+                account.locked += tx.amount;
+            }
+            TransactionKind::StakeRelease | TransactionKind::Slash => {
+                if account.locked < tx.amount { return Err(insufficient("escrow", account.locked)); }
+                if !self.escrows.remove(&escrow) { return Err(format!("Stake for {} is not locked", tx.task)); }
+                account.locked -= tx.amount;
+                if tx.kind == TransactionKind::StakeRelease { account.balance += tx.amount; }
+            }
+            TransactionKind::Burn => {
+                if account.balance < tx.amount { return Err(insufficient("balance", account.balance)); }
+                account.balance -= tx.amount;
+            }
+            TransactionKind::Mint => account.balance += tx.amount,
+            _ => account.nonce = account.nonce.max(tx.nonce), // Requests
+        }
+        Ok(())
+    }
+
+    pub fn apply_transactions(&mut self, transactions: &[Transaction]) -> Result<(), String> {
+        transactions.iter().try_for_each(|tx| self.apply_transaction(tx))
+    }
+
+    /// Commitment stored in every block header: accounts, then open escrows.
+    pub fn root(&self) -> String {
+        let encoder = self.accounts.iter().fold(
+            CanonicalEncoder::new("yuki-state-v3").u64(self.accounts.len() as u64),
+            |encoder, (address, account)| encoder.str(address).u64(account.balance).u64(account.locked).u64(account.nonce),
+        );
+        let encoder = self.escrows.iter().fold(
+            encoder.u64(self.escrows.len() as u64),
+            |encoder, (address, nonce)| encoder.str(address).u64(*nonce),
+        );
+        hash_bytes(&encoder.finish())
+    }
+}
+
+/// A wallet-signed request followed by the movements that settle it against
+/// `state`, in the order they must appear in a block. Claims are mined
+/// twice: pending, which locks the stake in escrow, then once decided.
+/// - emission permit: burn `tons * COST_PER_TON`
+/// - pending claim: stake lock
+/// - validated claim: stake release, mint of the methodology's reward
+/// - rejected claim: slash
+///
+/// A claim decided before its pending record was mined locks its stake in
+/// the same record. The reward is re-derived from the telemetry (nothing
+/// for a packet that corroborates another claim) and must match the amount
+/// the request carries.
+pub fn settle(request: &Transaction, stake_amount: u64, methodologies: &MethodologyRegistry, state: &LedgerState) -> Result<Vec<Transaction>, String> {
+    let movements = match (&request.kind, &request.status) {
+        (TransactionKind::EmissionPermit(permit), _) => {
+            let cost = emission_cost(permit.tons_to_emit).ok_or(format!("Permit {} costs more than the supply", request.task))?;
+            vec![(TransactionKind::Burn, cost)]
+        }
+        (kind, status) if kind.sentinel_id().is_some() => {
+            let reward = match request.corroborates {
+                Some(_) => 0,
+                None => methodologies.for_claim(kind).map_or(0, |m| m.credits(kind)),
+            };
+            if request.amount != reward {
+                return Err(format!("Claim {} credits {} Yuki but its telemetry earns {}", request.task, request.amount, reward));
+            }
+            let escrowed = state.is_escrowed(&request.sender, request.nonce);
+            let mut movements = if escrowed { vec![] } else { vec![(TransactionKind::StakeLock, stake_amount)] };
+            match status {
+                TaskStatus::PendingValidation if escrowed => return Err(format!("Stake for {} is already locked", request.task)),
+                TaskStatus::PendingValidation => {}
+                TaskStatus::Validated => movements.extend([(TransactionKind::StakeRelease, stake_amount), (TransactionKind::Mint, reward)]),
+                TaskStatus::Rejected => movements.push((TransactionKind::Slash, stake_amount)),
+                TaskStatus::UnderReview => return Err(format!("Claim {} is awaiting manual review", request.task)),
+            }
+            movements
+        }
+        (kind, _) => return Err(format!("{} transactions are not enabled", kind.name())),
+    };
+    let mut transactions = vec![request.clone()];
+    transactions.extend(movements.into_iter()
+        .filter(|(_, amount)| *amount > 0)
+        .map(|(kind, amount)| Transaction::movement(kind, request, amount)));
+    Ok(transactions)
+}
+
+/// Yuki burned for an emission permit of `tons`, or None if it overflows.
+pub fn emission_cost(tons: u64) -> Option<u64> {
+    tons.checked_mul(COST_PER_TON)
+}
+
+/// Genesis balances configured for a new ledger.
+pub fn load_genesis_allocations() -> BTreeMap<String, u64> {
+    utils::load_config(ALLOCATIONS_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::CarbonCaptureClaim;
+
+    fn funded(balance: u64) -> LedgerState {
+        LedgerState::genesis(&BTreeMap::from([("operator".to_string(), balance)]))
+    }
+
+    fn movement(kind: TransactionKind, nonce: u64, amount: u64) -> Transaction {
+        let mut request = Transaction::emission_permit("operator".to_string(), 0, nonce);
+        request.nonce = nonce; // Set when signing
+        Transaction::movement(kind, &request, amount)
+    }
+
+    #[test]
+    fn balances_follow_the_mined_movements() {
+        let mut state = funded(1_000);
+        state.apply_transactions(&[
+            movement(TransactionKind::StakeLock, 1, 500),
+            movement(TransactionKind::StakeLock, 2, 300),
+            movement(TransactionKind::StakeRelease, 1, 500),
+            movement(TransactionKind::Mint, 1, 250),
+            movement(TransactionKind::Slash, 2, 300),
+            movement(TransactionKind::Burn, 3, 100),
+        ]).unwrap();
+
+        assert_eq!(state.accounts["operator"], Account { balance: 850, locked: 0, nonce: 0 });
+        assert!(state.escrows.is_empty());
+    }
+
+    #[test]
+    fn movements_cannot_overdraw_or_touch_unlocked_stakes() {
+        let mut state = funded(400);
+        assert!(state.apply_transaction(&movement(TransactionKind::StakeLock, 1, 500)).is_err());
+        assert!(state.apply_transaction(&movement(TransactionKind::Burn, 1, 500)).is_err());
+        assert!(state.apply_transaction(&movement(TransactionKind::StakeRelease, 1, 0)).is_err(), "nothing locked");
+
+        state.apply_transaction(&movement(TransactionKind::StakeLock, 1, 100)).unwrap();
+        assert!(state.apply_transaction(&movement(TransactionKind::StakeLock, 1, 100)).is_err(), "locked twice");
+        assert!(state.apply_transaction(&movement(TransactionKind::Slash, 2, 100)).is_err(), "another request's stake");
+        assert_eq!(state.accounts["operator"], Account { balance: 300, locked: 100, nonce: 0 });
+        assert!(state.is_escrowed("operator", 1));
+    }
+
+    // A 10 tCO2 capture claim (1000 Yuki) from `operator`.
+    fn claim(status: TaskStatus) -> Transaction {
+        let kind = TransactionKind::CarbonCaptureClaim(CarbonCaptureClaim {
+            sentinel_id: "sentinel-1".to_string(),
+            tons_captured: 10.0,
+            measurement_start: None,
+            measurement_end: None,
+            location: None,
+            packet_id: None,
+            hardware_signature: String::new(),
+        });
+        let mut claim = Transaction::new("operator".to_string(), "carbon_capture-operator-1".to_string(), kind);
+        (claim.nonce, claim.amount, claim.status) = (1, 1_000, status);
+        claim
+    }
+
+    fn settled(request: &Transaction, state: &LedgerState) -> Result<Vec<(TransactionKind, u64)>, String> {
+        let txs = settle(request, 500, &MethodologyRegistry::default(), state)?;
+        Ok(txs[1..].iter().map(|tx| (tx.kind.clone(), tx.amount)).collect())
+    }
+
+    // The claim's stake locked by its pending record.
+    fn escrowed() -> LedgerState {
+        let mut state = funded(1_000);
+        state.apply_transaction(&movement(TransactionKind::StakeLock, 1, 500)).unwrap();
+        state
+    }
+
+    #[test]
+    fn requests_settle_with_explicit_movements() {
+        use TransactionKind::*;
+        let (open, escrowed) = (funded(1_000), escrowed());
+
+        assert_eq!(settled(&Transaction::emission_permit("operator".to_string(), 3, 1), &open).unwrap(), [(Burn, 300)]);
+        assert_eq!(settled(&claim(TaskStatus::PendingValidation), &open).unwrap(), [(StakeLock, 500)]);
+        assert!(settled(&claim(TaskStatus::PendingValidation), &escrowed).is_err(), "locked twice");
+        assert_eq!(settled(&claim(TaskStatus::Validated), &escrowed).unwrap(), [(StakeRelease, 500), (Mint, 1_000)]);
+        assert_eq!(settled(&claim(TaskStatus::Rejected), &escrowed).unwrap(), [(Slash, 500)]);
+        assert_eq!(settled(&claim(TaskStatus::Validated), &open).unwrap(), [(StakeLock, 500), (StakeRelease, 500), (Mint, 1_000)]);
+        assert!(settled(&claim(TaskStatus::UnderReview), &escrowed).is_err());
+    }
+
+    #[test]
+    fn settlement_rederives_the_reward() {
+        let escrowed = escrowed();
+        let mut inflated = claim(TaskStatus::Validated);
+        inflated.amount = 5_000;
+        assert!(settled(&inflated, &escrowed).unwrap_err().contains("earns 1000"));
+
+        let mut corroborating = claim(TaskStatus::Validated);
+        corroborating.corroborates = Some("carbon_capture-operator-7".to_string());
+        assert!(settled(&corroborating, &escrowed).is_err(), "credited to the claim it confirms");
+        corroborating.amount = 0;
+        assert_eq!(settled(&corroborating, &escrowed).unwrap(), [(TransactionKind::StakeRelease, 500)]);
+    }
+}
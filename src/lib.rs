@@ -0,0 +1,21 @@
+pub mod blockchain;
+pub mod wallet;
+pub mod p2p;
+pub mod marketplace; // We can keep this for viewing, but trading is disabled in logic
+pub mod transaction;
+pub mod utils;
+pub mod crypto;
+pub mod keystore;
+pub mod sentinel;
+pub mod merkle;
+pub mod proof;
+pub mod consensus;
+pub mod finality;
+pub mod ledger;
+pub mod methodology;
+pub mod anomaly;
+pub mod review;
+pub mod verdict;
+pub mod corroboration;
+pub mod facility;
+pub mod api;
@@ -1,24 +1,25 @@
-mod blockchain;
-mod wallet;
-mod p2p;
-mod marketplace; // We can keep this for viewing, but trading is disabled in logic
-mod transaction;
-mod utils;
-mod api; 
-
-use blockchain::{Blockchain, NetworkMessage};
-use p2p::{P2PEvent, YUKI_TOPIC};
+use yuki_blockchain::blockchain::{Blockchain, NetworkMessage};
+use yuki_blockchain::transaction::{CarbonCaptureClaim, MethaneClaim, Transaction, TransactionKind, WastewaterClaim};
+use yuki_blockchain::facility::{Facility, GeoPoint};
+use yuki_blockchain::p2p::{self, P2PEvent, P2PNetwork, YUKI_TOPIC};
+use yuki_blockchain::{api, sentinel};
 use std::error::Error;
 use libp2p::{
     gossipsub::{Event as GossipsubEvent, IdentTopic},
     swarm::SwarmEvent,
+    Swarm,
 };
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::select;
 use futures::StreamExt;
-use serde_json::json; 
 use std::sync::{Arc, Mutex}; 
-use rand::{distributions::Alphanumeric, Rng}; // For simulating signatures
+use rand::{distributions::Alphanumeric, Rng}; // For simulating packet ids
+
+fn publish(swarm: &mut Swarm<P2PNetwork>, msg: &NetworkMessage) {
+    if let Ok(json) = serde_json::to_string(msg) {
+        let _ = swarm.behaviour_mut().gossipsub.publish(IdentTopic::new(YUKI_TOPIC), json.as_bytes());
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -26,6 +27,27 @@ async fn main() -> Result<(), Box<dyn Error>> {
     
     // Initialize
     let blockchain = Arc::new(Mutex::new(Blockchain::new()));
+
+    // Validator wallet (keystore address) used to seal blocks on this node
+    if let Ok(validator) = std::env::var("YUKI_VALIDATOR") {
+        blockchain.lock().unwrap().producer = validator.trim().to_string();
+    }
+
+    // Yuki value above which claims need corroborating sentinels
+    if let Ok(threshold) = std::env::var("YUKI_CORROBORATION_THRESHOLD") {
+        match threshold.trim().parse() {
+            Ok(credits) => blockchain.lock().unwrap().corroboration.credit_threshold = credits,
+            Err(_) => println!("❌ Invalid YUKI_CORROBORATION_THRESHOLD '{}' (expected Yuki credits).", threshold),
+        }
+    }
+
+    // Headless nodes (API only) unlock the keystore from the environment
+    if let Ok(password) = std::env::var("YUKI_KEYSTORE_PASSWORD") {
+        match blockchain.lock().unwrap().wallets.unlock(&password) {
+            Ok(n) => println!("🔓 Keystore unlocked ({} keys).", n),
+            Err(e) => println!("❌ Keystore unlock failed: {}", e),
+        }
+    }
     let mut swarm = p2p::build_swarm()?;
     swarm.listen_on("/ip4/0.0.0.0/tcp/0".parse()?)?;
     
@@ -46,14 +68,21 @@ async fn main() -> Result<(), Box<dyn Error>> {
         println!("6.  Register New Wallet");
         println!("7.  Run Automated Compliance Check");
         println!("8.  Sync & Mine Block");
-        println!("9.  Exit");
+        println!("9.  Restore Wallet from Mnemonic");
+        let keystore_state = if blockchain.lock().unwrap().wallets.is_unlocked() { "unlocked" } else { "locked" };
+        println!("10. Unlock Keystore ({})", keystore_state);
+        println!("11. Lock Keystore");
+        println!("12. Register Sentinel Device");
+        println!("13. Review Quarantined Packets");
+        println!("14. Register Facility");
+        println!("0.  Exit");
 
         select! {
             line = stdin.next_line() => {
                 let choice = match line {
                     Ok(Some(line_str)) => line_str,
-                    Ok(None) => "9".to_string(),
-                    Err(_) => "9".to_string(),
+                    Ok(None) => "0".to_string(),
+                    Err(_) => "0".to_string(),
                 };
 
                 match choice.trim() {
@@ -65,45 +94,82 @@ async fn main() -> Result<(), Box<dyn Error>> {
                         println!("\nSelect Restoration Operation:");
                         println!("1. Carbon Capture (Direct Air Capture)");
                         println!("2. Industrial Wastewater Treatment");
+                        println!("3. Landfill Methane Destruction (Flare)");
                         let mut type_choice = String::new(); std::io::stdin().read_line(&mut type_choice)?;
 
                         // Simulate Hardware Data
                         let sentinel_id = "yuki-industrial-01"; 
-                        // Generate a random "signature" to simulate the Secure Element
-                        let signature: String = rand::thread_rng()
+                        // The simulated Secure Element signs the telemetry with the device key
+                        let device_key = sentinel::simulated_device_key(sentinel_id);
+                        // Random packet nonce so identical readings still get unique signatures
+                        let packet_id: String = rand::thread_rng()
                             .sample_iter(&Alphanumeric)
                             .take(16)
                             .map(char::from)
                             .collect();
 
-                        let (task_type, metadata) = match type_choice.trim() {
+                        println!("Measured over the last how many minutes?");
+                        let mut minutes = String::new(); std::io::stdin().read_line(&mut minutes)?;
+                        let measurement_end = chrono::Utc::now().timestamp();
+                        let measurement_start = measurement_end - 60 * minutes.trim().parse::<i64>().unwrap_or(60);
+
+                        let claim = match type_choice.trim() {
                             "1" => {
                                 println!("Tons of CO2 Captured?");
                                 let mut tons = String::new(); std::io::stdin().read_line(&mut tons)?;
-                                ("carbon_capture", json!({
-                                    "type": "carbon_capture",
-                                    "sentinel_id": sentinel_id,
-                                    "tons_captured": tons.trim().parse::<f64>().unwrap_or(0.0),
-                                    "hardware_signature": signature 
+                                Some(TransactionKind::CarbonCaptureClaim(CarbonCaptureClaim {
+                                    sentinel_id: sentinel_id.to_string(),
+                                    tons_captured: tons.trim().parse::<f64>().unwrap_or(0.0),
+                                    measurement_start: Some(measurement_start),
+                                    measurement_end: Some(measurement_end),
+                                    location: None, // The console sentinel has no GPS receiver
+                                    packet_id: Some(packet_id),
+                                    hardware_signature: String::new(),
                                 }))
                             },
                             "2" => {
                                 println!("Liters of Water Treated?");
                                 let mut lit = String::new(); std::io::stdin().read_line(&mut lit)?;
-                                ("wastewater_treatment", json!({
-                                    "type": "wastewater_treatment",
-                                    "sentinel_id": sentinel_id,
-                                    "liters_treated": lit.trim().parse::<u64>().unwrap_or(0),
-                                    "hardware_signature": signature
+                                Some(TransactionKind::WastewaterClaim(WastewaterClaim {
+                                    sentinel_id: sentinel_id.to_string(),
+                                    liters_treated: lit.trim().parse::<u64>().unwrap_or(0),
+                                    measurement_start: Some(measurement_start),
+                                    measurement_end: Some(measurement_end),
+                                    location: None, // The console sentinel has no GPS receiver
+                                    packet_id: Some(packet_id),
+                                    hardware_signature: String::new(),
+                                }))
+                            },
+                            "3" => {
+                                println!("Nm3 of CH4 sent to the flare?");
+                                let mut flow = String::new(); std::io::stdin().read_line(&mut flow)?;
+                                println!("Destruction efficiency (0-1)?");
+                                let mut eff = String::new(); std::io::stdin().read_line(&mut eff)?;
+                                Some(TransactionKind::MethaneClaim(MethaneClaim {
+                                    sentinel_id: sentinel_id.to_string(),
+                                    ch4_flow_nm3: flow.trim().parse::<f64>().unwrap_or(0.0),
+                                    destruction_efficiency: eff.trim().parse::<f64>().unwrap_or(0.0),
+                                    measurement_start: Some(measurement_start),
+                                    measurement_end: Some(measurement_end),
+                                    location: None, // The console sentinel has no GPS receiver
+                                    packet_id: Some(packet_id),
+                                    hardware_signature: String::new(),
                                 }))
                             },
-                            _ => ("unknown", json!({}))
+                            _ => None
                         };
 
-                        if task_type != "unknown" {
-                            let task_name = format!("{}-{}", task_type, chrono::Utc::now().timestamp());
-                            // LOCK & SUBMIT
-                            if let Some(tx) = blockchain.lock().unwrap().submit_industrial_task(wallet.trim(), task_name, metadata.to_string()) {
+                        if let Some(mut claim) = claim {
+                            sentinel::sign_telemetry(&device_key, &mut claim);
+                            // LOCK, SIGN & SUBMIT
+                            let mut bc = blockchain.lock().unwrap();
+                            let sender = wallet.trim().to_string();
+                            let nonce = bc.next_nonce(&sender);
+                            let task_name = Transaction::task_id(claim.name(), &sender, nonce);
+                            let mut tx = Transaction::new(sender, task_name, claim);
+                            if !bc.wallets.sign_transaction(&mut tx, nonce) {
+                                println!("❌ Cannot sign: keystore is locked or this node holds no key for the wallet.");
+                            } else if let Some(tx) = bc.submit_industrial_task(tx) {
                                 let msg = NetworkMessage::Transaction(tx);
                                 if let Ok(json) = serde_json::to_string(&msg) {
                                     let _ = swarm.behaviour_mut().gossipsub.publish(IdentTopic::new(YUKI_TOPIC), json.as_bytes());
@@ -118,8 +184,17 @@ async fn main() -> Result<(), Box<dyn Error>> {
                         println!("📉 EMISSION REQUEST: How many tons of CO2 to emit?");
                         let mut t = String::new(); std::io::stdin().read_line(&mut t)?;
                         
-                        // LOCK & BURN
-                        blockchain.lock().unwrap().request_emission_permit(w.trim(), t.trim().parse().unwrap_or(0));
+                        // LOCK, SIGN & BURN
+                        let mut bc = blockchain.lock().unwrap();
+                        let nonce = bc.next_nonce(w.trim());
+                        let mut permit = Transaction::emission_permit(w.trim().to_string(), t.trim().parse().unwrap_or(0), nonce);
+                        if bc.wallets.sign_transaction(&mut permit, nonce) {
+                            if let Some(permit) = bc.request_emission_permit(permit) {
+                                publish(&mut swarm, &NetworkMessage::Transaction(permit));
+                            }
+                        } else {
+                            println!("❌ Cannot sign: keystore is locked or this node holds no key for the wallet.");
+                        }
                     }
                     "3" => blockchain.lock().unwrap().view_wallets(),
                     "4" => blockchain.lock().unwrap().chain.iter().for_each(|block| println!("{:#?}", block)),
@@ -128,38 +203,140 @@ async fn main() -> Result<(), Box<dyn Error>> {
                         for peer in swarm.behaviour().mdns.discovered_nodes() { println!("{}", peer); }
                     },
                     "6" => {
-                        let w = blockchain.lock().unwrap().create_wallet();
-                        println!("✅ New Corporate Wallet Registered: {}", w.address);
+                        match blockchain.lock().unwrap().create_wallet() {
+                            Ok(w) => println!("✅ New Corporate Wallet Registered: {}", w.address),
+                            Err(e) => println!("❌ {}", e),
+                        }
                     }
                     "7" => {
-                        let results = blockchain.lock().unwrap().run_automated_validation();
-                        for (task_id, status) in results {
-                            let msg = NetworkMessage::ValidationResult(task_id, status);
-                            let _ = swarm.behaviour_mut().gossipsub.publish(IdentTopic::new(YUKI_TOPIC), serde_json::to_string(&msg).unwrap().as_bytes());
+                        let verdicts = blockchain.lock().unwrap().run_automated_validation();
+                        for verdict in verdicts {
+                            publish(&mut swarm, &NetworkMessage::ValidationResult(verdict));
                         }
                     }
                     "8" => {
-                        if let Some(block) = blockchain.lock().unwrap().mine_block() {
-                            let msg = NetworkMessage::Block(block);
-                            let _ = swarm.behaviour_mut().gossipsub.publish(IdentTopic::new(YUKI_TOPIC), serde_json::to_string(&msg).unwrap().as_bytes());
+                        let mut bc = blockchain.lock().unwrap();
+                        if let Some(block) = bc.mine_block() {
+                            publish(&mut swarm, &NetworkMessage::Block(block));
+                            // Our own prevote (and, with a quorum, precommit) for the new block
+                            for msg in bc.advance_finality() { publish(&mut swarm, &msg); }
+                        }
+                    }
+                    "9" => {
+                        println!("Enter the 24-word Mnemonic:");
+                        let mut phrase = String::new(); std::io::stdin().read_line(&mut phrase)?;
+                        match blockchain.lock().unwrap().restore_wallet(&phrase) {
+                            Ok(w) => println!("✅ Corporate Wallet Restored: {}", w.address),
+                            Err(e) => println!("❌ {}", e),
+                        }
+                    }
+                    "10" => {
+                        println!("Keystore Password:");
+                        let mut password = String::new(); std::io::stdin().read_line(&mut password)?;
+                        match blockchain.lock().unwrap().wallets.unlock(password.trim_end_matches(['\r', '\n'])) {
+                            Ok(n) => println!("🔓 Keystore unlocked ({} keys).", n),
+                            Err(e) => println!("❌ {}", e),
                         }
                     }
-                    "9" | "exit" => break,
+                    "11" => {
+                        blockchain.lock().unwrap().wallets.lock();
+                        println!("🔒 Keystore locked. Signing disabled until unlocked.");
+                    }
+                    "12" => {
+                        println!("Sentinel ID:");
+                        let mut id = String::new(); std::io::stdin().read_line(&mut id)?;
+                        println!("Device ed25519 Public Key (hex):");
+                        let mut key = String::new(); std::io::stdin().read_line(&mut key)?;
+                        println!("Facility ID (blank for a standalone device):");
+                        let mut facility = String::new(); std::io::stdin().read_line(&mut facility)?;
+                        match blockchain.lock().unwrap().register_sentinel(&id, &key, &facility) {
+                            Ok(()) => println!("✅ Sentinel '{}' added to the Industrial Registry.", id.trim()),
+                            Err(e) => println!("❌ {}", e),
+                        }
+                    }
+                    "13" => {
+                        let queue = blockchain.lock().unwrap().review_queue.clone();
+                        if queue.is_empty() {
+                            println!("✅ Review queue is empty.");
+                            continue;
+                        }
+                        for task in &queue {
+                            println!("• {} ({} Yuki) from {}: {}", task.task, task.amount, task.sender, task.kind.canonical_json());
+                        }
+                        println!("Task to Decide:");
+                        let mut task = String::new(); std::io::stdin().read_line(&mut task)?;
+                        println!("Approve? (y/n)");
+                        let mut approve = String::new(); std::io::stdin().read_line(&mut approve)?;
+                        println!("Reasoning:");
+                        let mut reasoning = String::new(); std::io::stdin().read_line(&mut reasoning)?;
+                        println!("Reviewer Wallet Address:");
+                        let mut reviewer = String::new(); std::io::stdin().read_line(&mut reviewer)?;
+
+                        let mut bc = blockchain.lock().unwrap();
+                        let Some(request) = queue.iter().find(|t| t.task == task.trim()) else {
+                            println!("❌ Task '{}' is not awaiting review.", task.trim());
+                            continue;
+                        };
+                        let Some(decision) = bc.sign_review(reviewer.trim(), request, approve.trim().eq_ignore_ascii_case("y"), reasoning.trim()) else {
+                            println!("❌ Cannot sign: keystore is locked or this node holds no key for the reviewer.");
+                            continue;
+                        };
+                        match bc.review_task(decision.clone()) {
+                            Ok(_) => publish(&mut swarm, &NetworkMessage::Review(decision)),
+                            Err(e) => println!("❌ {}", e),
+                        }
+                    }
+                    "14" => {
+                        println!("Facility ID:");
+                        let mut id = String::new(); std::io::stdin().read_line(&mut id)?;
+                        println!("Operator Wallet Address:");
+                        let mut operator = String::new(); std::io::stdin().read_line(&mut operator)?;
+                        println!("Equipment Capacity per Hour (e.g. carbon_capture=50,methane_destruction=5000):");
+                        let mut capacity = String::new(); std::io::stdin().read_line(&mut capacity)?;
+                        println!("Geofence as lat,lon;lat,lon;... (blank if not surveyed):");
+                        let mut fence = String::new(); std::io::stdin().read_line(&mut fence)?;
+
+                        let capacity = capacity.trim().split(',').filter_map(|entry| {
+                            let (task_type, per_hour) = entry.split_once('=')?;
+                            Some((task_type.trim().to_string(), per_hour.trim().parse::<f64>().ok()?))
+                        }).collect();
+                        let geofence = fence.trim().split(';').filter_map(|point| {
+                            let (lat, lon) = point.split_once(',')?;
+                            Some(GeoPoint { lat: lat.trim().parse().ok()?, lon: lon.trim().parse().ok()? })
+                        }).collect();
+                        let mut bc = blockchain.lock().unwrap();
+                        // Re-registering keeps the facility's installed sentinels
+                        let sentinels = bc.facilities.get(id.trim()).map(|f| f.sentinels.clone()).unwrap_or_default();
+                        let facility = Facility { id: id.trim().to_string(), operator: operator.trim().to_string(), geofence, capacity, sentinels };
+                        match bc.register_facility(facility) {
+                            Ok(()) => println!("✅ Facility '{}' registered.", id.trim()),
+                            Err(e) => println!("❌ {}", e),
+                        }
+                    }
+                    "0" | "exit" => break,
                     _ => println!("❌ Invalid Command."),
                 }
             },
             event = swarm.select_next_some() => {
-                match event {
-                    SwarmEvent::Behaviour(P2PEvent::Gossipsub(GossipsubEvent::Message { message, .. })) => {
-                        if let Ok(msg) = serde_json::from_slice::<NetworkMessage>(&message.data) {
-                            match msg {
-                                NetworkMessage::Block(b) => { println!("\n[NET] Ledger Update."); blockchain.lock().unwrap().add_block_from_network(b); },
-                                NetworkMessage::Transaction(t) => { println!("\n[NET] Incoming Telemetry."); blockchain.lock().unwrap().add_task_from_network(t); },
-                                NetworkMessage::ValidationResult(id, s) => { println!("\n[NET] Compliance Update."); blockchain.lock().unwrap().update_task_status_from_network(&id, s); }
-                            }
+                if let SwarmEvent::Behaviour(P2PEvent::Gossipsub(GossipsubEvent::Message { message, .. })) = event {
+                    if let Ok(msg) = serde_json::from_slice::<NetworkMessage>(&message.data) {
+                        match msg {
+                            NetworkMessage::Block(b) => {
+                                println!("\n[NET] Ledger Update.");
+                                let mut bc = blockchain.lock().unwrap();
+                                bc.add_block_from_network(b);
+                                for msg in bc.advance_finality() { publish(&mut swarm, &msg); }
+                            },
+                            NetworkMessage::Transaction(t) => { println!("\n[NET] Incoming Telemetry."); blockchain.lock().unwrap().add_task_from_network(t); },
+                            NetworkMessage::ValidationResult(v) => { println!("\n[NET] Compliance Update."); blockchain.lock().unwrap().update_task_status_from_network(v); },
+                            NetworkMessage::Vote(v) => {
+                                let replies = blockchain.lock().unwrap().add_vote_from_network(v);
+                                for msg in replies { publish(&mut swarm, &msg); }
+                            },
+                            NetworkMessage::Finality(c) => blockchain.lock().unwrap().add_certificate_from_network(c),
+                            NetworkMessage::Review(d) => { println!("\n[NET] Review Decision."); blockchain.lock().unwrap().add_review_from_network(d); },
                         }
-                    },
-                    _ => {}
+                    }
                 }
             }
         }
@@ -0,0 +1,121 @@
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+
+// RFC 6962 style domain separation: leaves and interior nodes hash with
+// different prefixes, so a node can never be passed off as a leaf.
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+pub fn leaf_hash(data: &[u8]) -> [u8; 32] {
+    Sha256::new().chain_update([LEAF_PREFIX]).chain_update(data).finalize().into()
+}
+
+pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new().chain_update([NODE_PREFIX]).chain_update(left).chain_update(right).finalize().into()
+}
+
+/// Root over already-hashed leaves. An odd node at the end of a level is
+/// promoted unchanged (no duplication, which would allow mutated trees).
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return Sha256::digest([]).into();
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [single] => *single,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// One sibling on the path from a leaf to the root.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProofStep {
+    pub side: Side,   // Which side the sibling sits on
+    pub hash: String, // Hex sibling hash
+}
+
+/// Sibling path for the leaf at `index`. Levels where the node was promoted
+/// (no sibling) contribute no step.
+pub fn merkle_proof(leaves: &[[u8; 32]], mut index: usize) -> Option<Vec<ProofStep>> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut path = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            let side = if sibling < index { Side::Left } else { Side::Right };
+            path.push(ProofStep { side, hash: hex::encode(level[sibling]) });
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    Some(path)
+}
+
+/// Fold a leaf hash up the path and compare with the expected hex root.
+pub fn verify_proof(leaf: [u8; 32], path: &[ProofStep], root: &str) -> bool {
+    let mut current = leaf;
+    for step in path {
+        let sibling: [u8; 32] = match hex::decode(&step.hash).ok().and_then(|b| b.try_into().ok()) {
+            Some(hash) => hash,
+            None => return false,
+        };
+        current = match step.side {
+            Side::Left => node_hash(&sibling, &current),
+            Side::Right => node_hash(&current, &sibling),
+        };
+    }
+    hex::encode(current) == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: usize) -> Vec<[u8; 32]> {
+        (0..count).map(|i| leaf_hash(format!("tx-{}", i).as_bytes())).collect()
+    }
+
+    #[test]
+    fn every_leaf_proves_against_the_root() {
+        for count in 1..=9 {
+            let leaves = leaves(count);
+            let root = hex::encode(merkle_root(&leaves));
+            for (index, leaf) in leaves.iter().enumerate() {
+                let path = merkle_proof(&leaves, index).unwrap();
+                assert!(verify_proof(*leaf, &path, &root), "leaf {} of {}", index, count);
+            }
+            assert!(merkle_proof(&leaves, count).is_none());
+        }
+    }
+
+    #[test]
+    fn proof_rejects_another_leaf() {
+        let leaves = leaves(7);
+        let root = hex::encode(merkle_root(&leaves));
+        let path = merkle_proof(&leaves, 6).unwrap();
+        assert!(!verify_proof(leaf_hash(b"tampered"), &path, &root));
+        assert!(!verify_proof(leaves[5], &path, &root));
+    }
+}
@@ -0,0 +1,280 @@
+use crate::transaction::TransactionKind;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::env;
+
+const METHANE_GWP_ENV: &str = "YUKI_METHANE_GWP"; // CO2e horizon for methane credits (gwp100 default, or gwp20)
+
+/// What the measuring equipment can physically report, in one packet and
+/// per sentinel over a rolling window (so splitting a reading into many
+/// short packets does not raise the ceiling).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicalLimits {
+    pub max_per_packet: f64,
+    pub max_per_window: f64,
+    pub window_secs: i64,
+    pub unit: &'static str,
+}
+
+/// A crediting methodology for one restoration activity. Adding an activity
+/// means a `TransactionKind` claim variant, an implementation of this trait
+/// and a `register` call; `Blockchain` only talks to the registry.
+pub trait Methodology: Send + Sync {
+    /// Claim type credited by this methodology (the `TransactionKind` tag).
+    fn task_type(&self) -> &'static str;
+
+    /// Limits of typical equipment. A facility's registered capacity
+    /// replaces the throughput figures (see `Facility::limits`).
+    fn limits(&self) -> PhysicalLimits;
+
+    /// The measured quantity in `limits().unit`, if the claim is ours.
+    fn measurement(&self, claim: &TransactionKind) -> Option<f64>;
+
+    /// Yuki credits earned by a validated claim.
+    fn credits(&self, claim: &TransactionKind) -> u64;
+
+    /// Methodology checks on attested telemetry. By default the measurement
+    /// must be present, non-negative and within the equipment's `limits`.
+    fn validate(&self, claim: &TransactionKind, limits: &PhysicalLimits) -> Result<(), String> {
+        check_physical_limits(self, claim, limits)
+    }
+}
+
+/// The default `validate`, for methodologies that add their own checks.
+pub fn check_physical_limits<M: Methodology + ?Sized>(methodology: &M, claim: &TransactionKind, limits: &PhysicalLimits) -> Result<(), String> {
+    let value = methodology.measurement(claim).ok_or(format!("Claim is not a {} measurement", methodology.task_type()))?;
+    if !(0.0..=limits.max_per_packet).contains(&value) {
+        return Err(format!(
+            "Reported {} {} exceeds physical limits of equipment ({} {} max per packet).",
+            value, limits.unit, limits.max_per_packet, limits.unit
+        ));
+    }
+    Ok(())
+}
+
+/// Direct Air Capture: 1 Ton Captured = 100 Yuki Credits.
+pub struct CarbonCapture;
+
+impl Methodology for CarbonCapture {
+    fn task_type(&self) -> &'static str { "carbon_capture" }
+
+    // A single unit typically captures max 50 tons/hour
+    fn limits(&self) -> PhysicalLimits {
+        PhysicalLimits { max_per_packet: 50.0, max_per_window: 50.0, window_secs: 3_600, unit: "tCO2" }
+    }
+
+    fn measurement(&self, claim: &TransactionKind) -> Option<f64> {
+        match claim {
+            TransactionKind::CarbonCaptureClaim(c) => Some(c.tons_captured),
+            _ => None,
+        }
+    }
+
+    fn credits(&self, claim: &TransactionKind) -> u64 {
+        self.measurement(claim).map_or(0, |tons| (tons * 100.0) as u64)
+    }
+}
+
+/// Industrial wastewater treatment: 1000 Liters = 1 Yuki Credit.
+pub struct WastewaterTreatment;
+
+impl Methodology for WastewaterTreatment {
+    fn task_type(&self) -> &'static str { "wastewater_treatment" }
+
+    // Hourly throughput of a large industrial treatment plant, 24 hours a day
+    fn limits(&self) -> PhysicalLimits {
+        PhysicalLimits { max_per_packet: 1_000_000.0, max_per_window: 24_000_000.0, window_secs: 86_400, unit: "L" }
+    }
+
+    fn measurement(&self, claim: &TransactionKind) -> Option<f64> {
+        match claim {
+            TransactionKind::WastewaterClaim(c) => Some(c.liters_treated as f64),
+            _ => None,
+        }
+    }
+
+    fn credits(&self, claim: &TransactionKind) -> u64 {
+        match claim {
+            TransactionKind::WastewaterClaim(c) => c.liters_treated / 1000,
+            _ => 0,
+        }
+    }
+}
+
+/// Global warming potential horizon used to express methane as CO2e.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Gwp {
+    #[default]
+    Gwp100,
+    Gwp20,
+}
+
+impl Gwp {
+    /// IPCC AR6 factors for non-fossil (biogenic) methane, as from landfills.
+    pub fn factor(&self) -> f64 {
+        match self {
+            Gwp::Gwp100 => 27.0,
+            Gwp::Gwp20 => 79.7,
+        }
+    }
+
+    /// Parse "gwp100" / "gwp20" (case-insensitive).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gwp100" | "100" => Some(Gwp::Gwp100),
+            "gwp20" | "20" => Some(Gwp::Gwp20),
+            _ => None,
+        }
+    }
+
+    /// Horizon from `YUKI_METHANE_GWP` (default GWP100) for a new ledger,
+    /// which stores it: an existing ledger keeps the horizon it was created with.
+    pub fn from_env() -> Self {
+        match env::var(METHANE_GWP_ENV) {
+            Ok(horizon) => Self::parse(&horizon)
+                .unwrap_or_else(|| panic!("Refusing to start: unknown {} '{}' (expected gwp100 or gwp20)", METHANE_GWP_ENV, horizon)),
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+const CH4_DENSITY_KG_PER_NM3: f64 = 0.7168; // At 0 °C, 1 atm
+const MAX_DESTRUCTION_EFFICIENCY: f64 = 0.995; // Best enclosed flares / engines
+
+/// Landfill methane flaring or combustion: destroyed CH4 converted to
+/// tCO2e with the configured GWP; 1 tCO2e = 100 Yuki Credits.
+pub struct MethaneDestruction {
+    pub gwp: Gwp,
+}
+
+impl MethaneDestruction {
+    pub fn new(gwp: Gwp) -> Self {
+        Self { gwp }
+    }
+
+    /// Tonnes of CO2-equivalent destroyed by the claim.
+    pub fn co2e_tons(&self, claim: &TransactionKind) -> Option<f64> {
+        match claim {
+            TransactionKind::MethaneClaim(c) => {
+                Some(c.ch4_flow_nm3 * CH4_DENSITY_KG_PER_NM3 * c.destruction_efficiency * self.gwp.factor() / 1000.0)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Methodology for MethaneDestruction {
+    fn task_type(&self) -> &'static str { "methane_destruction" }
+
+    // Hourly CH4 throughput of a large landfill gas flare station
+    fn limits(&self) -> PhysicalLimits {
+        PhysicalLimits { max_per_packet: 5_000.0, max_per_window: 5_000.0, window_secs: 3_600, unit: "Nm3 CH4" }
+    }
+
+    fn measurement(&self, claim: &TransactionKind) -> Option<f64> {
+        match claim {
+            TransactionKind::MethaneClaim(c) => Some(c.ch4_flow_nm3),
+            _ => None,
+        }
+    }
+
+    fn credits(&self, claim: &TransactionKind) -> u64 {
+        self.co2e_tons(claim).map_or(0, |tons| (tons * 100.0) as u64)
+    }
+
+    fn validate(&self, claim: &TransactionKind, limits: &PhysicalLimits) -> Result<(), String> {
+        check_physical_limits(self, claim, limits)?;
+        if let TransactionKind::MethaneClaim(c) = claim {
+            if !(0.0..=MAX_DESTRUCTION_EFFICIENCY).contains(&c.destruction_efficiency) {
+                return Err(format!(
+                    "Reported destruction efficiency {} is implausible (0 to {} allowed).",
+                    c.destruction_efficiency, MAX_DESTRUCTION_EFFICIENCY
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Methodologies keyed by claim type.
+pub struct MethodologyRegistry {
+    methodologies: HashMap<&'static str, Box<dyn Methodology>>,
+}
+
+impl MethodologyRegistry {
+    pub fn empty() -> Self {
+        Self { methodologies: HashMap::new() }
+    }
+
+    /// Add (or replace) the methodology for its task type.
+    pub fn register(&mut self, methodology: Box<dyn Methodology>) {
+        self.methodologies.insert(methodology.task_type(), methodology);
+    }
+
+    pub fn get(&self, task_type: &str) -> Option<&dyn Methodology> {
+        self.methodologies.get(task_type).map(|m| m.as_ref())
+    }
+
+    pub fn for_claim(&self, claim: &TransactionKind) -> Option<&dyn Methodology> {
+        self.get(claim.name())
+    }
+
+    /// The built-in methodologies, crediting methane at `gwp`.
+    pub fn with_methane_gwp(gwp: Gwp) -> Self {
+        let mut registry = Self::default();
+        registry.register(Box::new(MethaneDestruction::new(gwp)));
+        registry
+    }
+}
+
+impl Default for MethodologyRegistry {
+    /// The built-in methodologies.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Box::new(CarbonCapture));
+        registry.register(Box::new(WastewaterTreatment));
+        registry.register(Box::new(MethaneDestruction::new(Gwp::default())));
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::MethaneClaim;
+
+    // 1000 Nm3 of CH4 (716.8 kg) at 99% destruction efficiency
+    fn flare_reading() -> TransactionKind {
+        TransactionKind::MethaneClaim(MethaneClaim {
+            sentinel_id: "flare-1".to_string(),
+            ch4_flow_nm3: 1_000.0,
+            destruction_efficiency: 0.99,
+            measurement_start: None,
+            measurement_end: None,
+            location: None,
+            packet_id: None,
+            hardware_signature: String::new(),
+        })
+    }
+
+    #[test]
+    fn methane_is_converted_with_the_configured_horizon() {
+        let gwp100 = MethaneDestruction::new(Gwp::Gwp100);
+        let gwp20 = MethaneDestruction::new(Gwp::Gwp20);
+
+        assert!((gwp100.co2e_tons(&flare_reading()).unwrap() - 19.160064).abs() < 1e-9);
+        assert!((gwp20.co2e_tons(&flare_reading()).unwrap() - 56.5576704).abs() < 1e-9);
+        assert_eq!(gwp100.credits(&flare_reading()), 1_916);
+        assert_eq!(gwp20.credits(&flare_reading()), 5_655);
+        assert_eq!(MethodologyRegistry::with_methane_gwp(Gwp::Gwp20).for_claim(&flare_reading()).unwrap().credits(&flare_reading()), 5_655);
+    }
+
+    #[test]
+    fn horizon_is_stored_by_name() {
+        assert_eq!(serde_json::to_string(&Gwp::Gwp20).unwrap(), "\"gwp20\"");
+        assert_eq!(serde_json::from_str::<Gwp>("\"gwp100\"").unwrap(), Gwp::Gwp100);
+        assert_eq!(Gwp::parse(" GWP20 "), Some(Gwp::Gwp20));
+        assert_eq!(Gwp::parse("gwp500"), None);
+    }
+}
@@ -28,6 +28,7 @@ pub struct P2PNetwork {
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum P2PEvent {
     Gossipsub(GossipsubEvent),
     Mdns(MdnsEvent),
@@ -0,0 +1,30 @@
+use crate::blockchain::BlockHeader;
+use crate::merkle::{self, ProofStep};
+use crate::transaction::Transaction;
+use serde::{Serialize, Deserialize};
+
+/// Everything an auditor needs to check that a credit transaction is on the
+/// ledger without downloading `chain.json`: the transaction, its block header
+/// and the Merkle path to the header's transactions root.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionProof {
+    pub block_hash: String,
+    pub header: BlockHeader,
+    pub transaction: Transaction,
+    pub leaf_index: usize,
+    pub path: Vec<ProofStep>,
+}
+
+/// Standalone verifier. A passing proof ties the transaction to `block_hash`;
+/// the caller still has to trust that hash (e.g. compare it with a header
+/// obtained from a node they trust).
+pub fn verify_transaction_proof(proof: &TransactionProof) -> Result<(), String> {
+    if proof.header.hash() != proof.block_hash {
+        return Err("Header does not hash to the claimed block hash".to_string());
+    }
+    let leaf = merkle::leaf_hash(&proof.transaction.canonical_bytes());
+    if !merkle::verify_proof(leaf, &proof.path, &proof.header.transactions_root) {
+        return Err("Merkle path does not lead to the header's transactions root".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::crypto::{self, CanonicalEncoder};
+use crate::transaction::{TaskStatus, Transaction};
+use crate::utils;
+use serde::{Serialize, Deserialize};
+
+const REVIEWERS_FILE: &str = "reviewers.json"; // JSON array of hex ed25519 reviewer public keys
+
+/// A human reviewer's verdict on a packet held in the review queue. It is
+/// signed by the reviewer's key and mined with the claim, so the slash or
+/// mint that follows can be traced to a named reviewer and their reasoning.
+/// The signature covers the claim's request hash, so a decision cannot be
+/// replayed onto another claim that reuses the task id.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReviewDecision {
+    pub task: String,
+    pub request_hash: String, // Transaction::request_hash of the reviewed claim
+    pub approve: bool,
+    pub reasoning: String,
+    pub reviewer: String, // Hex ed25519 public key (must be in the reviewer set)
+    pub timestamp: i64,
+    pub signature: String,
+}
+
+impl ReviewDecision {
+    pub fn payload(task: &str, request_hash: &str, approve: bool, reasoning: &str, timestamp: i64) -> Vec<u8> {
+        CanonicalEncoder::new("yuki-review-v2")
+            .str(task)
+            .str(request_hash)
+            .str(if approve { "approve" } else { "reject" })
+            .str(reasoning)
+            .u64(timestamp as u64)
+            .finish()
+    }
+
+    pub fn verify(&self, reviewers: &[String]) -> Result<(), String> {
+        if !reviewers.contains(&self.reviewer) {
+            return Err("Signer is not an authorized reviewer".to_string());
+        }
+        crypto::verify(&self.reviewer, &Self::payload(&self.task, &self.request_hash, self.approve, &self.reasoning, self.timestamp), &self.signature)
+            .map(|_| ())
+    }
+
+    /// Whether the decision was signed for this exact request.
+    pub fn is_for(&self, request: &Transaction) -> bool {
+        self.task == request.task && self.request_hash == request.request_hash()
+    }
+
+    /// Status the reviewed claim is settled with.
+    pub fn status(&self) -> TaskStatus {
+        if self.approve { TaskStatus::Validated } else { TaskStatus::Rejected }
+    }
+}
+
+/// Reviewer set configured for genesis.
+pub fn load_reviewer_set() -> Vec<String> {
+    let reviewers: Vec<String> = utils::load_config(REVIEWERS_FILE);
+    reviewers.into_iter().map(|r| r.trim().to_lowercase()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decision_without_request_hash_does_not_deserialize() {
+        let unbound = r#"{"task":"t","approve":true,"reasoning":"ok","reviewer":"00","timestamp":0,"signature":"00"}"#;
+        assert!(serde_json::from_str::<ReviewDecision>(unbound).is_err());
+    }
+}
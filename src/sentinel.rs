@@ -0,0 +1,111 @@
+use crate::crypto::{self, CanonicalEncoder};
+use crate::methodology::PhysicalLimits;
+use crate::transaction::TransactionKind;
+use ed25519_dalek::SigningKey;
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use std::env;
+
+const DEV_SENTINELS_ENV: &str = "YUKI_DEV_SENTINELS";
+
+/// An accepted measurement, kept per sentinel to enforce rolling-window limits.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TelemetryRecord {
+    pub task: String,
+    pub start: i64,    // Measurement period, Unix seconds
+    pub end: i64,
+    pub quantity: f64, // In the methodology's unit
+}
+
+/// Canonical bytes a sentinel signs: the claim's canonical JSON without its
+/// `hardware_signature` field. serde_json maps are key-sorted, so the
+/// serialization is independent of field order in the submitted packet.
+pub fn telemetry_signing_payload(claim: &TransactionKind) -> Vec<u8> {
+    let mut payload = claim.clone();
+    payload.set_hardware_signature(String::new());
+    CanonicalEncoder::new("yuki-telemetry-v1")
+        .str(&payload.canonical_json())
+        .finish()
+}
+
+/// Sign a telemetry claim in place, as the device's secure element would.
+pub fn sign_telemetry(key: &SigningKey, claim: &mut TransactionKind) {
+    let signature = crypto::sign(key, &telemetry_signing_payload(claim));
+    claim.set_hardware_signature(signature);
+}
+
+/// Check the claim's `hardware_signature` against the device public key.
+pub fn verify_telemetry(public_key: &str, claim: &TransactionKind) -> Result<(), String> {
+    let signature = claim.hardware_signature().filter(|s| !s.is_empty()).ok_or("Missing Hardware Signature")?;
+    crypto::verify(public_key, &telemetry_signing_payload(claim), signature).map(|_| ())
+}
+
+/// DEVELOPMENT ONLY: deterministic key for the simulated console sentinel.
+/// Anyone can recompute it, so production registries must not trust it.
+pub fn simulated_device_key(sentinel_id: &str) -> SigningKey {
+    let seed: [u8; 32] = Sha256::digest(format!("yuki-simulated-sentinel:{}", sentinel_id)).into();
+    SigningKey::from_bytes(&seed)
+}
+
+/// Whether `public_key` is the publicly derivable simulated key of `sentinel_id`.
+pub fn is_simulated_key(sentinel_id: &str, public_key: &str) -> bool {
+    hex::encode(simulated_device_key(sentinel_id).verifying_key().as_bytes()) == public_key
+}
+
+/// Simulated device keys are only trusted on development nodes started
+/// with `YUKI_DEV_SENTINELS=1`.
+pub fn simulated_keys_allowed() -> bool {
+    env::var(DEV_SENTINELS_ENV).is_ok_and(|v| v.trim() == "1")
+}
+
+/// Check a new measurement over `(start, end)` against the sentinel's
+/// accepted history: periods may not overlap (the same hour cannot be
+/// reported twice) and the total over the rolling window ending at `end`
+/// must stay within `limits.max_per_window`.
+pub fn check_rate_limits(history: &[TelemetryRecord], (start, end): (i64, i64), quantity: f64, limits: &PhysicalLimits) -> Result<(), String> {
+    if let Some(overlap) = history.iter().find(|r| r.start < end && start < r.end) {
+        return Err(format!("Measurement period overlaps packet {} from this sentinel.", overlap.task));
+    }
+    let window_start = end - limits.window_secs;
+    let total = quantity + history.iter().filter(|r| r.end > window_start).map(|r| r.quantity).sum::<f64>();
+    if total > limits.max_per_window {
+        return Err(format!(
+            "Sentinel reported {} {} within {}s (limit {} {} per {}s).",
+            total, limits.unit, limits.window_secs, limits.max_per_window, limits.unit, limits.window_secs
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 3_600;
+
+    fn accepted(task: &str, start: i64, quantity: f64) -> TelemetryRecord {
+        TelemetryRecord { task: task.to_string(), start, end: start + HOUR, quantity }
+    }
+
+    #[test]
+    fn periods_may_not_overlap_accepted_packets() {
+        let limits = PhysicalLimits { max_per_packet: 50.0, max_per_window: 1_000.0, window_secs: 24 * HOUR, unit: "tCO2" };
+        let history = [accepted("morning", 10 * HOUR, 10.0)];
+
+        let overlap = check_rate_limits(&history, (10 * HOUR + 1_800, 11 * HOUR + 1_800), 10.0, &limits).unwrap_err();
+        assert!(overlap.contains("overlaps packet morning"), "{}", overlap);
+        assert!(check_rate_limits(&history, (9 * HOUR, 12 * HOUR), 10.0, &limits).is_err(), "enclosing period");
+        assert!(check_rate_limits(&history, (11 * HOUR, 12 * HOUR), 10.0, &limits).is_ok(), "adjacent period");
+    }
+
+    #[test]
+    fn rolling_window_total_is_capped() {
+        let limits = PhysicalLimits { max_per_packet: 50.0, max_per_window: 100.0, window_secs: 4 * HOUR, unit: "tCO2" };
+        let history = [accepted("first", 0, 40.0), accepted("second", HOUR, 40.0)];
+
+        assert!(check_rate_limits(&history, (2 * HOUR, 3 * HOUR), 20.0, &limits).is_ok(), "exactly at the limit");
+        assert!(check_rate_limits(&history, (2 * HOUR, 3 * HOUR), 21.0, &limits).is_err());
+        // Four hours later the first packet has left the window
+        assert!(check_rate_limits(&history, (4 * HOUR, 5 * HOUR), 60.0, &limits).is_ok());
+    }
+}
@@ -1,3 +1,8 @@
+use crate::crypto::{self, CanonicalEncoder};
+use crate::facility::GeoPoint;
+use crate::review::ReviewDecision;
+use crate::utils::hash_bytes;
+use ed25519_dalek::SigningKey;
 use serde::{Serialize, Deserialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -5,27 +10,514 @@ pub enum TaskStatus {
     PendingValidation,
     Validated,
     Rejected,
+    UnderReview, // Held in the review queue for a human decision
+}
+
+impl TaskStatus {
+    /// Stable name used in canonical encodings (independent of Debug output).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::PendingValidation => "pending_validation",
+            TaskStatus::Validated => "validated",
+            TaskStatus::Rejected => "rejected",
+            TaskStatus::UnderReview => "under_review",
+        }
+    }
+}
+
+/// Why a claim was rejected (and its stake slashed), in machine-readable form.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RejectionCode {
+    UnauthorizedHardware,
+    InvalidPacket,
+    UnregisteredFacility,
+    OperatorMismatch,
+    OutsideGeofence,
+    AttestationFailed,
+    ReplayAttack,
+    UnsupportedActivity,
+    PhysicalLimits,
+    RateLimit,
+    Uncorroborated,
+    ReviewerRejected,
+}
+
+/// Rejection kept per task and gossiped with the signed validation verdict,
+/// so the submitting corporation can find out why its stake was slashed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RejectionRecord {
+    pub task: String,
+    pub code: RejectionCode,
+    pub message: String,
+    pub validator: String, // Hex public key of the validator, or of the reviewer for manual rejections
+    pub timestamp: i64,
+}
+
+/// Direct Air Capture telemetry, signed by the sentinel's secure element.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CarbonCaptureClaim {
+    pub sentinel_id: String,
+    pub tons_captured: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_start: Option<i64>, // Unix seconds the reading covers (from)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_end: Option<i64>,   // Unix seconds the reading covers (until)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<GeoPoint>,     // GPS fix of the device, if it has a receiver
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packet_id: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hardware_signature: String,
+}
+
+/// Industrial wastewater treatment telemetry, signed by the sentinel.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WastewaterClaim {
+    pub sentinel_id: String,
+    pub liters_treated: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_start: Option<i64>, // Unix seconds the reading covers (from)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_end: Option<i64>,   // Unix seconds the reading covers (until)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<GeoPoint>,     // GPS fix of the device, if it has a receiver
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packet_id: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hardware_signature: String,
+}
+
+/// Landfill gas flare / engine telemetry: methane routed to destruction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MethaneClaim {
+    pub sentinel_id: String,
+    pub ch4_flow_nm3: f64,          // Measured CH4 volume at normal conditions
+    pub destruction_efficiency: f64, // Fraction of the CH4 destroyed (0..1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_start: Option<i64>, // Unix seconds the reading covers (from)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_end: Option<i64>,   // Unix seconds the reading covers (until)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<GeoPoint>,     // GPS fix of the device, if it has a receiver
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packet_id: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hardware_signature: String,
+}
+
+/// Burn request: the protocol charges `tons_to_emit * COST_PER_TON`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EmissionPermit {
+    pub tons_to_emit: u64,
+}
+
+/// Wallet-to-wallet payment (not enabled on this network).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Transfer {
+    pub to: String,
+    pub amount: u64,
+}
+
+/// Protocol parameter change proposal (not enabled on this network).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Governance {
+    pub proposal: String,
+}
+
+/// What a transaction is. The first six are requests signed by a wallet;
+/// the rest are balance movements the block producer derives from them, so
+/// every debit and credit is visible on-chain. The JSON tag values match the
+/// legacy `proof_metadata` "type" field.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransactionKind {
+    #[serde(rename = "carbon_capture")]
+    CarbonCaptureClaim(CarbonCaptureClaim),
+    #[serde(rename = "wastewater_treatment")]
+    WastewaterClaim(WastewaterClaim),
+    #[serde(rename = "methane_destruction")]
+    MethaneClaim(MethaneClaim),
+    EmissionPermit(EmissionPermit),
+    Transfer(Transfer),
+    Governance(Governance),
+    StakeLock,    // Available balance -> escrow
+    StakeRelease, // Escrow -> available balance
+    Slash,        // Escrow destroyed (rejected claim)
+    Burn,         // Balance destroyed (emission permit)
+    Mint,         // New credits (validated claim)
+}
+
+impl TransactionKind {
+    /// Stable name (the JSON tag).
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransactionKind::CarbonCaptureClaim(_) => "carbon_capture",
+            TransactionKind::WastewaterClaim(_) => "wastewater_treatment",
+            TransactionKind::MethaneClaim(_) => "methane_destruction",
+            TransactionKind::EmissionPermit(_) => "emission_permit",
+            TransactionKind::Transfer(_) => "transfer",
+            TransactionKind::Governance(_) => "governance",
+            TransactionKind::StakeLock => "stake_lock",
+            TransactionKind::StakeRelease => "stake_release",
+            TransactionKind::Slash => "slash",
+            TransactionKind::Burn => "burn",
+            TransactionKind::Mint => "mint",
+        }
+    }
+
+    /// Signed by a wallet, as opposed to a protocol movement.
+    pub fn is_request(&self) -> bool {
+        matches!(
+            self,
+            TransactionKind::CarbonCaptureClaim(_)
+                | TransactionKind::WastewaterClaim(_)
+                | TransactionKind::MethaneClaim(_)
+                | TransactionKind::EmissionPermit(_)
+                | TransactionKind::Transfer(_)
+                | TransactionKind::Governance(_)
+        )
+    }
+
+    /// Sentinel that produced the telemetry, for claims.
+    pub fn sentinel_id(&self) -> Option<&str> {
+        match self {
+            TransactionKind::CarbonCaptureClaim(c) => Some(&c.sentinel_id),
+            TransactionKind::WastewaterClaim(c) => Some(&c.sentinel_id),
+            TransactionKind::MethaneClaim(c) => Some(&c.sentinel_id),
+            _ => None,
+        }
+    }
+
+    /// Device signature over the telemetry, for claims (empty if unsigned).
+    pub fn hardware_signature(&self) -> Option<&str> {
+        match self {
+            TransactionKind::CarbonCaptureClaim(c) => Some(&c.hardware_signature),
+            TransactionKind::WastewaterClaim(c) => Some(&c.hardware_signature),
+            TransactionKind::MethaneClaim(c) => Some(&c.hardware_signature),
+            _ => None,
+        }
+    }
+
+    /// Device-generated packet nonce, for claims that carry one.
+    pub fn packet_id(&self) -> Option<&str> {
+        match self {
+            TransactionKind::CarbonCaptureClaim(c) => c.packet_id.as_deref(),
+            TransactionKind::WastewaterClaim(c) => c.packet_id.as_deref(),
+            TransactionKind::MethaneClaim(c) => c.packet_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// GPS position reported with the telemetry, for claims that carry one.
+    pub fn location(&self) -> Option<GeoPoint> {
+        match self {
+            TransactionKind::CarbonCaptureClaim(c) => c.location,
+            TransactionKind::WastewaterClaim(c) => c.location,
+            TransactionKind::MethaneClaim(c) => c.location,
+            _ => None,
+        }
+    }
+
+    /// Measurement period `(start, end)` in Unix seconds, for claims that carry one.
+    pub fn measurement_window(&self) -> Option<(i64, i64)> {
+        let (start, end) = match self {
+            TransactionKind::CarbonCaptureClaim(c) => (c.measurement_start, c.measurement_end),
+            TransactionKind::WastewaterClaim(c) => (c.measurement_start, c.measurement_end),
+            TransactionKind::MethaneClaim(c) => (c.measurement_start, c.measurement_end),
+            _ => return None,
+        };
+        Some((start?, end?))
+    }
+
+    pub fn set_hardware_signature(&mut self, signature: String) {
+        match self {
+            TransactionKind::CarbonCaptureClaim(c) => c.hardware_signature = signature,
+            TransactionKind::WastewaterClaim(c) => c.hardware_signature = signature,
+            TransactionKind::MethaneClaim(c) => c.hardware_signature = signature,
+            _ => {}
+        }
+    }
+
+    /// Key-sorted compact JSON, identical to the legacy `proof_metadata`
+    /// strings, so signatures and hashes over them stay valid.
+    pub fn canonical_json(&self) -> String {
+        serde_json::to_value(self).map(|v| v.to_string()).unwrap_or_default()
+    }
+}
+
+/// Signed envelope proving the sender's wallet key authorized the transaction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Authorization {
+    pub public_key: String, // Hex ed25519 public key (hashes to the sender address)
+    pub signature: String,  // Hex ed25519 signature over Transaction::signing_payload
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(try_from = "TransactionRecord")]
 pub struct Transaction {
-    pub sender: String,
-    pub receiver: String,
+    pub sender: String, // Signing wallet, or the account a movement changes
     pub amount: u64,
     pub task: String,
-    pub proof_metadata: String,
+    pub kind: TransactionKind,
     pub status: TaskStatus, // Replaced 'verified: bool'
+    #[serde(default)]
+    pub nonce: u64, // Per-sender sequence number, strictly increasing (anti-replay)
+    #[serde(default)]
+    pub authorization: Option<Authorization>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<ReviewDecision>, // Reviewer's signed verdict, for claims decided manually
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub corroborated_by: Vec<String>,   // Packet ids of the sentinels that confirmed a high-value claim
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corroborates: Option<String>,   // Task this packet confirmed instead of being credited itself
+}
+
+/// Wire form accepted when decoding: the typed `kind`, or the JSON string
+/// `proof_metadata` of baseline ledgers.
+#[derive(Deserialize)]
+struct TransactionRecord {
+    sender: String,
+    #[serde(default)]
+    amount: u64,
+    task: String,
+    #[serde(default)]
+    kind: Option<TransactionKind>,
+    #[serde(default)]
+    proof_metadata: String,
+    status: TaskStatus,
+    #[serde(default)]
+    nonce: u64,
+    #[serde(default)]
+    authorization: Option<Authorization>,
+    #[serde(default)]
+    review: Option<ReviewDecision>,
+    #[serde(default)]
+    corroborated_by: Vec<String>,
+    #[serde(default)]
+    corroborates: Option<String>,
+}
+
+impl TryFrom<TransactionRecord> for Transaction {
+    type Error = String;
+
+    fn try_from(record: TransactionRecord) -> Result<Self, String> {
+        let kind = match record.kind {
+            Some(kind) => kind,
+            None => serde_json::from_str(&record.proof_metadata)
+                .map_err(|e| format!("Undecodable proof_metadata for {} ({})", record.task, e))?,
+        };
+        Ok(Self {
+            sender: record.sender,
+            amount: record.amount,
+            task: record.task,
+            kind,
+            status: record.status,
+            nonce: record.nonce,
+            authorization: record.authorization,
+            review: record.review,
+            corroborated_by: record.corroborated_by,
+            corroborates: record.corroborates,
+        })
+    }
 }
 
 impl Transaction {
-    pub fn new(sender: String, receiver: String, amount: u64, task: String, proof_metadata: String) -> Self {
+    pub fn new(sender: String, task: String, kind: TransactionKind) -> Self {
         Self {
             sender,
-            receiver,
-            amount,
+            amount: 0,
             task,
-            proof_metadata,
+            kind,
             status: TaskStatus::PendingValidation, // Default to pending
+            nonce: 0,
+            authorization: None,
+            review: None,
+            corroborated_by: Vec::new(),
+            corroborates: None,
+        }
+    }
+
+    /// Burn request for an emission permit; the cost is computed by the protocol.
+    pub fn emission_permit(sender: String, tons_to_emit: u64, nonce: u64) -> Self {
+        let task = Self::task_id("emission_permit", &sender, nonce);
+        Self::new(sender, task, TransactionKind::EmissionPermit(EmissionPermit { tons_to_emit }))
+    }
+
+    /// Task id of a wallet's `nonce`-th request, the only one nodes accept for it.
+    pub fn task_id(kind: &str, sender: &str, nonce: u64) -> String {
+        format!("{}-{}-{}", kind, sender, nonce)
+    }
+
+    /// Protocol balance movement settling `request`. Unsigned: validators
+    /// re-derive it from the signed request instead of trusting it.
+    pub fn movement(kind: TransactionKind, request: &Transaction, amount: u64) -> Self {
+        Self {
+            sender: request.sender.clone(),
+            amount,
+            task: request.task.clone(),
+            kind,
+            status: request.status.clone(),
+            nonce: request.nonce,
+            authorization: None,
+            review: None,
+            corroborated_by: Vec::new(),
+            corroborates: None,
+        }
+    }
+
+    /// Drop what validators assign after submission: a claim enters the
+    /// pools pending, undecided and uncorroborated; a burn needs no decision.
+    pub fn reset_to_submitted(&mut self) {
+        self.status = match self.kind {
+            TransactionKind::EmissionPermit(_) => TaskStatus::Validated,
+            _ => TaskStatus::PendingValidation,
+        };
+        self.review = None;
+        self.corroborated_by.clear();
+        self.corroborates = None;
+    }
+
+    /// Tons requested, if this is an emission permit (burn) request.
+    pub fn tons_to_emit(&self) -> Option<u64> {
+        match &self.kind {
+            TransactionKind::EmissionPermit(permit) => Some(permit.tons_to_emit),
+            _ => None,
         }
     }
-}
\ No newline at end of file
+
+    /// Whether this record decides the request. A claim is first mined
+    /// pending, which only locks its stake, and again once decided.
+    pub fn is_settled(&self) -> bool {
+        self.status != TaskStatus::PendingValidation
+    }
+
+    /// Canonical bytes signed by the sender. Amount and status are assigned
+    /// by the protocol after submission, so they are not covered.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        CanonicalEncoder::new("yuki-tx-v1")
+            .str(&self.sender)
+            .str(&self.task)
+            .str(&self.kind.canonical_json())
+            .u64(self.nonce)
+            .finish()
+    }
+
+    /// SHA256 (hex) of the signing payload: names the request whatever
+    /// status and amount the protocol later assigns to it.
+    pub fn request_hash(&self) -> String {
+        hash_bytes(&self.signing_payload())
+    }
+
+    /// Canonical encoding of every field, as committed to by the block's
+    /// transactions Merkle root.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let (public_key, signature) = match &self.authorization {
+            Some(auth) => (auth.public_key.as_str(), auth.signature.as_str()),
+            None => ("", ""),
+        };
+        let encoder = CanonicalEncoder::new("yuki-tx-record-v3")
+            .str(&self.sender)
+            .u64(self.amount)
+            .str(&self.task)
+            .str(&self.kind.canonical_json())
+            .str(self.status.as_str())
+            .u64(self.nonce)
+            .str(public_key)
+            .str(signature);
+        let encoder = match &self.review {
+            Some(review) => encoder
+                .u64(1)
+                .str(&review.task)
+                .str(&review.request_hash)
+                .str(if review.approve { "approve" } else { "reject" })
+                .str(&review.reasoning)
+                .str(&review.reviewer)
+                .u64(review.timestamp as u64)
+                .str(&review.signature),
+            None => encoder.u64(0),
+        };
+        let encoder = self.corroborated_by.iter()
+            .fold(encoder.u64(self.corroborated_by.len() as u64), |encoder, id| encoder.str(id));
+        match &self.corroborates {
+            Some(task) => encoder.u64(1).str(task),
+            None => encoder.u64(0),
+        }
+        .finish()
+    }
+
+    pub fn sign(&mut self, key: &SigningKey, nonce: u64) {
+        self.nonce = nonce;
+        let signature = crypto::sign(key, &self.signing_payload());
+        self.authorization = Some(Authorization {
+            public_key: hex::encode(key.verifying_key().as_bytes()),
+            signature,
+        });
+    }
+
+    /// Reject anything not signed by the key that owns the sender address.
+    pub fn verify_signature(&self) -> Result<(), String> {
+        let auth = self.authorization.as_ref().ok_or("Transaction is not signed")?;
+        let public_key = crypto::verify(&auth.public_key, &self.signing_payload(), &auth.signature)?;
+        if crypto::address_from_public_key(&public_key) != self.sender {
+            return Err("Signing key does not belong to the sender wallet".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn baseline_proof_metadata_decodes_to_a_typed_claim() {
+        let baseline = r#"{"sender":"b84c669e","receiver":"System-Reward-Pool","amount":1000,"task":"carbon_capture-1765352793",
+            "proof_metadata":"{\"hardware_signature\":\"SIG\",\"sentinel_id\":\"yuki-industrial-01\",\"tons_captured\":10.0,\"type\":\"carbon_capture\"}",
+            "status":"Validated"}"#;
+        let tx: Transaction = serde_json::from_str(baseline).unwrap();
+
+        assert_eq!(tx.sender, "b84c669e");
+        assert_eq!(tx.amount, 1_000);
+        assert_eq!(tx.status, TaskStatus::Validated);
+        assert_eq!(tx.nonce, 0);
+        let TransactionKind::CarbonCaptureClaim(claim) = &tx.kind else { panic!("decoded as {}", tx.kind.name()) };
+        assert_eq!((claim.sentinel_id.as_str(), claim.tons_captured, claim.hardware_signature.as_str()), ("yuki-industrial-01", 10.0, "SIG"));
+
+        let undecodable = r#"{"sender":"b84c669e","task":"tree_planting-1","proof_metadata":"{\"type\":\"tree_planting\"}","status":"PendingValidation"}"#;
+        assert!(serde_json::from_str::<Transaction>(undecodable).is_err());
+    }
+
+    #[test]
+    fn typed_transactions_round_trip() {
+        let permit = Transaction::emission_permit("wallet".to_string(), 3, 7);
+        let mint = Transaction::movement(TransactionKind::Mint, &permit, 300);
+        for tx in [permit, mint] {
+            let decoded: Transaction = serde_json::from_str(&serde_json::to_string(&tx).unwrap()).unwrap();
+            assert_eq!(decoded.canonical_bytes(), tx.canonical_bytes());
+        }
+    }
+
+    #[test]
+    fn only_the_sender_key_over_the_signed_fields_verifies() {
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let sender = crypto::address_from_public_key(&key.verifying_key());
+        let mut permit = Transaction::emission_permit(sender.clone(), 3, 1);
+        assert!(permit.verify_signature().is_err(), "unsigned");
+
+        permit.sign(&key, 1);
+        assert!(permit.verify_signature().is_ok());
+
+        let mut raised = permit.clone();
+        raised.kind = TransactionKind::EmissionPermit(EmissionPermit { tons_to_emit: 300 });
+        assert!(raised.verify_signature().is_err());
+
+        let mut replayed = permit.clone();
+        replayed.nonce = 2;
+        assert!(replayed.verify_signature().is_err());
+
+        let mut impostor = Transaction::emission_permit(sender, 3, 1);
+        impostor.sign(&SigningKey::from_bytes(&[6u8; 32]), 1);
+        assert_eq!(impostor.verify_signature().unwrap_err(), "Signing key does not belong to the sender wallet");
+    }
+}
@@ -1,8 +1,28 @@
+use serde::de::DeserializeOwned;
 use sha2::{Sha256, Digest};
+use std::fs;
+use std::io::ErrorKind;
 
 /// Hash a string with SHA256
 pub fn hash_data(data: &str) -> String {
+    hash_bytes(data.as_bytes())
+}
+
+/// Hash raw bytes with SHA256
+pub fn hash_bytes(data: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(data);
     format!("{:x}", hasher.finalize())
 }
+
+/// Genesis configuration from a JSON file, or the default (empty) value when
+/// the file does not exist. A file that exists but cannot be read or parsed
+/// stops the node instead of silently becoming an empty set.
+pub fn load_config<T: DeserializeOwned + Default>(path: &str) -> T {
+    match fs::read_to_string(path) {
+        Ok(data) => serde_json::from_str(&data)
+            .unwrap_or_else(|e| panic!("Refusing to start: {} is malformed ({})", path, e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => T::default(),
+        Err(e) => panic!("Refusing to start: cannot read {} ({})", path, e),
+    }
+}
@@ -0,0 +1,39 @@
+use crate::crypto::{self, CanonicalEncoder};
+use crate::transaction::{RejectionRecord, TaskStatus, Transaction};
+use serde::{Serialize, Deserialize};
+
+/// An automated validator's decision on a pending claim, gossiped so peers
+/// move the claim on without validating it themselves. It carries the claim
+/// as decided (status and corroboration) and is signed by a key in the
+/// validator set; peers only apply it to the pending claim with the same
+/// request hash.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ValidationVerdict {
+    pub claim: Transaction,
+    pub rejection: Option<RejectionRecord>, // Why, for rejected claims
+    pub validator: String,                  // Hex ed25519 public key (must be in the validator set)
+    pub signature: String,
+}
+
+impl ValidationVerdict {
+    pub fn payload(claim: &Transaction, rejection: Option<&RejectionRecord>) -> Vec<u8> {
+        let rejection = rejection.and_then(|r| serde_json::to_value(r).ok()).map_or(String::new(), |v| v.to_string());
+        CanonicalEncoder::new("yuki-verdict-v1")
+            .bytes(&claim.canonical_bytes())
+            .str(&rejection)
+            .finish()
+    }
+
+    pub fn verify(&self, validators: &[String]) -> Result<(), String> {
+        if !validators.contains(&self.validator) {
+            return Err("Signer is not an authorized validator".to_string());
+        }
+        match (&self.claim.status, &self.rejection) {
+            (TaskStatus::Rejected, Some(record)) if record.task == self.claim.task && record.validator == self.validator => {}
+            (TaskStatus::Validated | TaskStatus::UnderReview, None) => {}
+            _ => return Err(format!("Verdict on {} is inconsistent with its rejection record", self.claim.task)),
+        }
+        crypto::verify(&self.validator, &Self::payload(&self.claim, self.rejection.as_ref()), &self.signature)
+            .map(|_| ())
+    }
+}
@@ -1,3 +1,6 @@
+use crate::crypto::{address_from_public_key, signing_key_from_mnemonic};
+use crate::keystore::Keystore;
+use crate::transaction::Transaction;
 use bip39::Mnemonic;
 use rand::{RngCore, thread_rng};
 use serde::{Serialize, Deserialize};
@@ -12,15 +15,18 @@ pub struct Wallet {
     pub balance_yuki: u64,
     pub balance_yg: u64,
     pub balance_yt: u64,
+    #[serde(default)]
+    pub public_key: String, // Hex ed25519 public key (empty for legacy wallets)
 }
 
 impl Wallet {
-    pub fn new(address: String) -> Self {
+    pub fn new(address: String, public_key: String) -> Self {
         Self {
             address,
-            balance_yuki: 10,
+            balance_yuki: 0, // Cached from the ledger state
             balance_yg: 0,
             balance_yt: 0,
+            public_key,
         }
     }
 }
@@ -28,17 +34,37 @@ impl Wallet {
 // --- ADDED #[derive(Default)] HERE ---
 #[derive(Default)] 
 pub struct WalletManager {
-    wallets: HashMap<String, Wallet>,
+    wallets: HashMap<String, Wallet>, // Public data only; secrets live in the keystore
+    keystore: Keystore,
 }
 
 impl WalletManager {
     pub fn new() -> Self {
         let wallets = Self::load_wallets();
-        Self { wallets }
+        let keystore = Keystore::load();
+        Self { wallets, keystore }
     }
 
-    /// Create a new wallet from random 32 bytes => 24 words in bip39
-    pub fn create_wallet(&mut self) -> Wallet {
+    pub fn unlock(&mut self, password: &str) -> Result<usize, String> {
+        self.keystore.unlock(password)
+    }
+
+    pub fn lock(&mut self) {
+        self.keystore.lock();
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.keystore.is_unlocked()
+    }
+
+    /// Create a new wallet from random 32 bytes => 24 words in bip39.
+    /// The ed25519 keypair is derived from the mnemonic seed (SLIP-0010),
+    /// and the address is the hash of the public key. Requires an unlocked keystore.
+    pub fn create_wallet(&mut self) -> Result<Wallet, String> {
+        if !self.keystore.is_unlocked() {
+            return Err("Keystore is locked. Unlock it first.".to_string());
+        }
+
         let mut rng = thread_rng();
         let mut entropy = [0u8; 32];
         rng.fill_bytes(&mut entropy);
@@ -48,10 +74,52 @@ impl WalletManager {
         let words = mnemonic.to_string(); 
         println!("Mnemonic (save this!): {}", words);
 
-        let address = crate::utils::hash_data(&format!("{:?}", entropy));
-        let wallet = Wallet::new(address.clone());
-        self.wallets.insert(address.clone(), wallet.clone());
-        wallet
+        let signing_key = signing_key_from_mnemonic(&mnemonic);
+        let public_key = signing_key.verifying_key();
+        let address = address_from_public_key(&public_key);
+
+        let wallet = Wallet::new(address.clone(), hex::encode(public_key.as_bytes()));
+        self.keystore.insert(address.clone(), signing_key)?;
+        self.wallets.insert(address, wallet.clone());
+        Ok(wallet)
+    }
+
+    /// Rebuild a wallet (address and keys) from the 24 words shown at creation.
+    /// An existing wallet keeps its balances; a missing one is re-registered.
+    pub fn restore_from_mnemonic(&mut self, phrase: &str) -> Result<Wallet, String> {
+        let mnemonic = Mnemonic::parse_normalized(phrase.trim())
+            .map_err(|e| format!("Invalid mnemonic: {}", e))?;
+
+        let signing_key = signing_key_from_mnemonic(&mnemonic);
+        let public_key = signing_key.verifying_key();
+        let address = address_from_public_key(&public_key);
+        self.keystore.insert(address.clone(), signing_key)?;
+
+        let wallet = self.wallets
+            .entry(address.clone())
+            .or_insert_with(|| Wallet::new(address.clone(), String::new()));
+        wallet.public_key = hex::encode(public_key.as_bytes());
+        Ok(wallet.clone())
+    }
+
+    /// Sign with the locally held key of the transaction's sender wallet.
+    pub fn sign_transaction(&self, transaction: &mut Transaction, nonce: u64) -> bool {
+        match self.keystore.get(&transaction.sender) {
+            Some(key) => { transaction.sign(key, nonce); true }
+            None => false,
+        }
+    }
+
+    /// Hex public key of a wallet whose key is held (and unlocked) locally.
+    pub fn local_public_key(&self, address: &str) -> Option<String> {
+        self.keystore.get(address).map(|key| hex::encode(key.verifying_key().as_bytes()))
+    }
+
+    /// Sign arbitrary bytes with a locally held wallet key.
+    /// Returns (hex public key, hex signature).
+    pub fn sign_bytes(&self, address: &str, message: &[u8]) -> Option<(String, String)> {
+        let key = self.keystore.get(address)?;
+        Some((hex::encode(key.verifying_key().as_bytes()), crate::crypto::sign(key, message)))
     }
 
     pub fn get_mut_wallet(&mut self, address: &str) -> Option<&mut Wallet> {